@@ -0,0 +1,388 @@
+//! Generates the `Field` enum and the `FieldAttr` table of easytradeview from scanner metainfo snapshots.
+//!
+//! A snapshot is the response of `POST https://scanner.tradingview.com/{screener}/metainfo` with a `{}` body.
+//! Only the regions between the `// @generated begin` and `// @generated end` markers of `field.rs`
+//! and `field_attr.rs` are rewritten. Existing fields keep their variant name and position; columns
+//! new to the table are appended, named after their wire name.
+//!
+//! The generator doesn't depend on easytradeview, so that it still builds when the sources it rewrites don't.
+//!
+//!     cargo run -p easytradeview-codegen -- --snapshot crypto.json --snapshot america.json
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde_json::Value;
+
+#[derive(Parser)]
+struct Opts {
+    /// Metainfo snapshots to merge into the field table.
+    #[arg(long)]
+    snapshot: Vec<PathBuf>,
+
+    /// Source directory of easytradeview.
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../src"))]
+    src: PathBuf,
+
+    /// Fails instead of writing if the sources are not up to date.
+    #[arg(long, default_value_t = false)]
+    check: bool,
+}
+
+/// A row of the `FieldAttr` table.
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    variant: String,
+    name: String,
+    has_interval: bool,
+    kind: String,
+}
+
+impl Row {
+    fn to_code(&self) -> String {
+        format!(
+            "        FieldAttr::new(Field::{}, {:?}, {}, {}),",
+            self.variant, self.name, self.has_interval, self.kind
+        )
+    }
+}
+
+const TEXT: &str = "ValueKind::Text";
+
+/// A column of a metainfo snapshot.
+#[derive(Debug, Clone, PartialEq)]
+struct Column {
+    name: String,
+    has_interval: bool,
+    kind: String,
+}
+
+/// Get the `ValueKind` expression of a metainfo type, as `FieldInfo::value_kind` of easytradeview does.
+/// Text with enum values is an enum; sets, maps and unknown types are text.
+fn kind_expr(field_type: &str, values: &Value) -> String {
+    let has_enum_values = values
+        .as_array()
+        .is_some_and(|x| x.iter().any(|x| x.is_string() || x["id"].is_string()));
+    let kind = match field_type {
+        "number" => "Number",
+        "price" | "fundamental_price" => "Price",
+        "percent" => "Percent",
+        "integer" => "Integer",
+        "text" if has_enum_values => "Enum",
+        "bool" => "Bool",
+        "time" => "Timestamp",
+        _ => "Text",
+    };
+    format!("ValueKind::{}", kind)
+}
+
+/// Ranks kinds from the least to the most specific: text, number, then the others, by name.
+fn kind_rank(kind: &str) -> (u8, &str) {
+    match kind {
+        TEXT => (0, kind),
+        "ValueKind::Number" => (1, kind),
+        _ => (2, kind),
+    }
+}
+
+/// Get the more specific of two kinds, whatever their order.
+fn merge_kind(a: &str, b: &str) -> String {
+    kind_rank(a).max(kind_rank(b)).1.to_owned()
+}
+
+/// Parses a metainfo snapshot, `{"fields": [{"n": name, "t": type, "r": values}]}`.
+/// The endpoint lists a column once per interval, e.g. `RSI` and `RSI|60`, which are merged into one
+/// column taking an interval.
+fn parse_snapshot(json: &Value) -> Vec<Column> {
+    let mut columns: Vec<Column> = Vec::new();
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    for data in json["fields"].as_array().into_iter().flatten() {
+        let Some(name) = data["n"].as_str() else {
+            continue;
+        };
+        let (name, has_interval) = match name.split_once('|') {
+            Some((name, _)) => (name, true),
+            None => (name, false),
+        };
+        let kind = kind_expr(data["t"].as_str().unwrap_or_default(), &data["r"]);
+        match indexes.get(name) {
+            Some(&index) => {
+                let column = &mut columns[index];
+                column.has_interval |= has_interval;
+                column.kind = merge_kind(&column.kind, &kind);
+            }
+            None => {
+                indexes.insert(name.to_owned(), columns.len());
+                columns.push(Column {
+                    name: name.to_owned(),
+                    has_interval,
+                    kind,
+                });
+            }
+        }
+    }
+    columns
+}
+
+/// Derives a variant name from a wire name, e.g. `Candle.Doji.Dragonfly` to `CandleDojiDragonfly`.
+fn variant_name(name: &str) -> String {
+    let mut variant = String::new();
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(c) = chars.next() {
+            variant.push(c.to_ascii_uppercase());
+            variant.extend(chars);
+        }
+    }
+    if !variant.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        variant.insert_str(0, "Field");
+    }
+    variant
+}
+
+/// Splits a source file around its generated region, returning the parts before, in and after it.
+fn split_region<'a>(source: &'a str, region: &str) -> Result<(&'a str, &'a str, &'a str)> {
+    let begin = format!("// @generated begin: {}\n", region);
+    let end = format!("// @generated end: {}", region);
+    let start = source
+        .find(&begin)
+        .map(|x| x + begin.len())
+        .with_context(|| format!("no begin marker of {}", region))?;
+    let end = start
+        + source[start..]
+            .find(&end)
+            .with_context(|| format!("no end marker of {}", region))?;
+    // The region stops before the indentation of the end marker.
+    let stop = source[..end]
+        .rfind('\n')
+        .map_or(start, |x| x + 1)
+        .max(start);
+    Ok((&source[..start], &source[start..stop], &source[stop..]))
+}
+
+/// Parses the rows of the generated region of `field_attr.rs`.
+fn parse_rows(region: &str) -> Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    for line in region.lines().map(|x| x.trim()).filter(|x| !x.is_empty()) {
+        let args = line
+            .strip_prefix("FieldAttr::new(")
+            .and_then(|x| x.strip_suffix("),"))
+            .with_context(|| format!("unexpected line: {}", line))?;
+        let args: Vec<&str> = args.splitn(4, ", ").collect();
+        let [field, name, has_interval, kind] = args.as_slice() else {
+            bail!("unexpected arguments: {}", line);
+        };
+        rows.push(Row {
+            variant: field
+                .strip_prefix("Field::")
+                .with_context(|| format!("unexpected field: {}", line))?
+                .to_owned(),
+            name: serde_json::from_str(name)
+                .with_context(|| format!("unexpected name: {}", line))?,
+            has_interval: has_interval.parse()?,
+            kind: kind.to_string(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Merges the columns of a snapshot into the rows, updating known columns and appending new ones.
+/// The first `fixed` rows come from the sources: their kind is only set if it is text, as other kinds may
+/// have been set by hand. Other rows take the most specific kind of any snapshot, so that the order of the
+/// snapshots doesn't matter. A column takes an interval if any snapshot lists it with one, as screeners may differ.
+fn merge(rows: &mut Vec<Row>, fixed: usize, columns: &[Column]) {
+    let mut variants: HashSet<String> = rows.iter().map(|x| x.variant.clone()).collect();
+    variants.insert("Undefined".to_owned());
+    variants.insert("UndefinedWithoutInterval".to_owned());
+    for column in columns {
+        if let Some(index) = rows.iter().position(|x| x.name == column.name) {
+            let row = &mut rows[index];
+            row.has_interval |= column.has_interval;
+            if index >= fixed || row.kind == TEXT {
+                row.kind = merge_kind(&row.kind, &column.kind);
+            }
+            continue;
+        }
+        let base = variant_name(&column.name);
+        let mut variant = base.clone();
+        let mut n = 2;
+        while variants.contains(&variant) {
+            variant = format!("{}{}", base, n);
+            n += 1;
+        }
+        variants.insert(variant.clone());
+        rows.push(Row {
+            variant,
+            name: column.name.clone(),
+            has_interval: column.has_interval,
+            kind: column.kind.clone(),
+        });
+    }
+}
+
+/// Get the sources of `field.rs` and `field_attr.rs` with their generated regions rewritten from the rows.
+fn generate(field_src: &str, field_attr_src: &str, rows: &[Row]) -> Result<(String, String)> {
+    let (before, _, after) = split_region(field_src, "fields")?;
+    let variants: String = rows
+        .iter()
+        .map(|x| format!("    {},\n", x.variant))
+        .collect();
+    let field_src = format!("{}{}{}", before, variants, after);
+
+    let (before, _, after) = split_region(field_attr_src, "field attrs")?;
+    let attrs: String = rows.iter().map(|x| x.to_code() + "\n").collect();
+    let field_attr_src = format!("{}{}{}", before, attrs, after);
+    Ok((field_src, field_attr_src))
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn main() -> Result<()> {
+    let opts = Opts::parse();
+    let field_path = opts.src.join("field.rs");
+    let field_attr_path = opts.src.join("field_attr.rs");
+    let field_src = read(&field_path)?;
+    let field_attr_src = read(&field_attr_path)?;
+
+    let mut rows = parse_rows(split_region(&field_attr_src, "field attrs")?.1)?;
+    let count = rows.len();
+    for path in &opts.snapshot {
+        let json = serde_json::from_str(&read(path)?)
+            .with_context(|| format!("invalid snapshot {}", path.display()))?;
+        merge(&mut rows, count, &parse_snapshot(&json));
+    }
+
+    let (new_field_src, new_field_attr_src) = generate(&field_src, &field_attr_src, &rows)?;
+    let changed = new_field_src != field_src || new_field_attr_src != field_attr_src;
+    if opts.check {
+        if changed {
+            bail!("the field table is not up to date");
+        }
+        return Ok(());
+    }
+    std::fs::write(&field_path, new_field_src)?;
+    std::fs::write(&field_attr_path, new_field_attr_src)?;
+    println!("{} fields, {} new", rows.len(), rows.len() - count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sources() -> Result<(String, String)> {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("../src");
+        Ok((
+            read(&src.join("field.rs"))?,
+            read(&src.join("field_attr.rs"))?,
+        ))
+    }
+
+    #[test]
+    fn test_variant_name() {
+        assert_eq!(variant_name("Candle.Doji.Dragonfly"), "CandleDojiDragonfly");
+        assert_eq!(
+            variant_name("relative_volume_10d_calc"),
+            "RelativeVolume10dCalc"
+        );
+        assert_eq!(variant_name("RSI[1]"), "RSI1");
+        assert_eq!(variant_name("52_week_high"), "Field52WeekHigh");
+    }
+
+    #[test]
+    fn test_generate_is_stable() -> Result<()> {
+        let (field_src, field_attr_src) = sources()?;
+        let rows = parse_rows(split_region(&field_attr_src, "field attrs")?.1)?;
+        assert!(rows
+            .iter()
+            .any(|x| x.variant == "ADXplusDI" && x.name == "ADX+DI"));
+        let generated = generate(&field_src, &field_attr_src, &rows)?;
+        assert!(generated == (field_src, field_attr_src));
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_snapshot() -> Result<()> {
+        let (field_src, field_attr_src) = sources()?;
+        let mut rows = parse_rows(split_region(&field_attr_src, "field attrs")?.1)?;
+        let count = rows.len();
+        let json = json!({
+            "fields": [
+                {"n": "close", "t": "number"},
+                {"n": "RSI|60", "t": "number"},
+                {"n": "sector", "t": "text"},
+                {"n": "ADX DI", "t": "interface"},
+            ]
+        });
+        merge(&mut rows, count, &parse_snapshot(&json));
+        assert_eq!(rows.len(), count + 2);
+        let close = rows
+            .iter()
+            .find(|x| x.name == "close")
+            .context("no close")?;
+        // The kind of the table is kept, the snapshot only knows it is a number.
+        assert_eq!(close.kind, "ValueKind::Price");
+        assert_eq!(
+            rows[count].to_code().trim(),
+            r#"FieldAttr::new(Field::Sector, "sector", false, ValueKind::Text),"#
+        );
+        assert_eq!(rows[count + 1].variant, "ADXDI");
+        assert_eq!(rows[count + 1].kind, "ValueKind::Text");
+
+        let (field_src, field_attr_src) = generate(&field_src, &field_attr_src, &rows)?;
+        assert!(field_src.contains("    Sector,\n    ADXDI,\n    // @generated end: fields"));
+        assert_eq!(
+            parse_rows(split_region(&field_attr_src, "field attrs")?.1)?,
+            rows
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_kinds() -> Result<()> {
+        let (_, field_attr_src) = sources()?;
+        let source_rows = parse_rows(split_region(&field_attr_src, "field attrs")?.1)?;
+        let count = source_rows.len();
+        let crypto = json!({
+            "fields": [
+                {"n": "type", "t": "text"},
+                {"n": "description", "t": "text", "r": ["a", "b"]},
+                {"n": "sector", "t": "text"},
+                {"n": "float_shares", "t": "number"},
+            ]
+        });
+        let america = json!({
+            "fields": [
+                {"n": "sector", "t": "text", "r": [{"id": "tech", "name": "Technology"}]},
+                {"n": "float_shares", "t": "fundamental_price"},
+            ]
+        });
+        let kind =
+            |rows: &[Row], name: &str| rows.iter().find(|x| x.name == name).map(|x| x.kind.clone());
+
+        for snapshots in [[&crypto, &america], [&america, &crypto]] {
+            let mut rows = source_rows.clone();
+            for json in snapshots {
+                merge(&mut rows, count, &parse_snapshot(json));
+            }
+            assert_eq!(rows.len(), count + 2);
+            // A hand-set enum is not downgraded, while text is upgraded.
+            assert_eq!(kind(&rows, "type").as_deref(), Some("ValueKind::Enum"));
+            assert_eq!(
+                kind(&rows, "description").as_deref(),
+                Some("ValueKind::Enum")
+            );
+            // New columns take the most specific kind of any snapshot.
+            assert_eq!(kind(&rows, "sector").as_deref(), Some("ValueKind::Enum"));
+            assert_eq!(
+                kind(&rows, "float_shares").as_deref(),
+                Some("ValueKind::Price")
+            );
+        }
+        Ok(())
+    }
+}
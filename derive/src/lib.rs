@@ -0,0 +1,152 @@
+//! `#[derive(ScanRow)]` for easytradeview, decoding the values of a scanned symbol into a struct.
+//!
+//! Each field of the struct is a column, named after the field unless given `#[tv(field = "...")]`,
+//! with the interval of `#[tv(interval = "...")]` on the field, or else on the struct, or else the
+//! default interval. A field marked `#[tv(symbol)]` gets the ticker of the symbol instead.
+//! Unknown fields or intervals, and intervals given to fields without interval, are reported as errors
+//! by `ScanRow::columns`.
+//!
+//! ```ignore
+//! #[derive(ScanRow)]
+//! #[tv(interval = "60")]
+//! struct Row {
+//!     #[tv(symbol)]
+//!     ticker: Ticker,
+//!     close: f64,
+//!     #[tv(field = "RSI", interval = "240")]
+//!     rsi_4h: Option<f64>,
+//!     #[tv(field = "type")]
+//!     symbol_type: String,
+//! }
+//! ```
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(ScanRow, attributes(tv))]
+pub fn derive_scan_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of a `#[tv(...)]` attribute.
+#[derive(Default)]
+struct TvAttr {
+    field: Option<LitStr>,
+    interval: Option<LitStr>,
+    symbol: bool,
+}
+
+impl TvAttr {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<TvAttr> {
+        let mut tv = TvAttr::default();
+        for attr in attrs.iter().filter(|x| x.path().is_ident("tv")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("field") {
+                    tv.field = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("interval") {
+                    tv.interval = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("symbol") {
+                    tv.symbol = true;
+                } else {
+                    return Err(meta.error("expected `field`, `interval` or `symbol`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(tv)
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "ScanRow requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "ScanRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let struct_attr = TvAttr::parse(&input.attrs)?;
+    if struct_attr.field.is_some() || struct_attr.symbol {
+        return Err(syn::Error::new_spanned(
+            input,
+            "only `interval` is allowed on the struct",
+        ));
+    }
+    let default_interval = match &struct_attr.interval {
+        Some(interval) => quote!(::easytradeview::Interval::parse(#interval)?),
+        None => quote!(::easytradeview::Interval::default()),
+    };
+
+    let mut columns = Vec::new();
+    let mut decoders = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let attr = TvAttr::parse(&field.attrs)?;
+        if attr.symbol {
+            if attr.field.is_some() || attr.interval.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "`symbol` cannot be combined with `field` or `interval`",
+                ));
+            }
+            decoders.push(quote! {
+                #ident: ::std::convert::From::from(values.symbol().clone())
+            });
+            continue;
+        }
+
+        let name = attr
+            .field
+            .unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span()));
+        let field = quote!(::easytradeview::Field::parse(#name)?);
+        columns.push(match &attr.interval {
+            Some(interval) => quote! {
+                ::easytradeview::FieldWithInterval::try_new(
+                    #field,
+                    ::easytradeview::Interval::parse(#interval)?,
+                )?
+            },
+            None => quote!(#field.with_interval(&default_interval)),
+        });
+        let column = columns.len() - 1;
+        decoders.push(quote! {
+            #ident: ::easytradeview::FromScanValue::from_scan_value(values, &columns[#column])?
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::easytradeview::ScanRow for #ident #ty_generics #where_clause {
+            fn columns() -> ::easytradeview::Result<::std::vec::Vec<::easytradeview::FieldWithInterval>> {
+                let default_interval = #default_interval;
+                let _ = &default_interval;
+                ::std::result::Result::Ok(::std::vec![#(#columns),*])
+            }
+
+            fn from_values(
+                values: &::easytradeview::TimedSymbolValues,
+                columns: &[::easytradeview::FieldWithInterval],
+            ) -> ::easytradeview::Result<Self> {
+                let _ = columns;
+                ::std::result::Result::Ok(Self {
+                    #(#decoders),*
+                })
+            }
+        }
+    })
+}
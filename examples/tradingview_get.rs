@@ -128,7 +128,7 @@ async fn main() -> Result<()> {
                 for field in fields.iter() {
                     let val = vals
                         .values()
-                        .get(&field)
+                        .get(field)
                         .map_or("".into(), |x| x.to_string());
                     row.push(val);
                 }
@@ -176,9 +176,9 @@ async fn main() -> Result<()> {
                 .collect();
             let mut table = Table::new(&headers);
             for vals in symbols {
-                let mut row = vec![vals.symbol().to_owned()];
+                let mut row = vec![vals.symbol().to_string()];
                 for field in fields.iter() {
-                    let val = vals.values().get(&field).map_or("".into(), |x| {
+                    let val = vals.values().get(field).map_or("".into(), |x| {
                         if x.is_f64() {
                             format!("{:.3}", x.as_f64().unwrap_or(0.0))
                         } else {
@@ -233,7 +233,7 @@ async fn main() -> Result<()> {
                 );
             } else {
                 for (k, v) in values {
-                    println!("{:>13} : {}", k, v.to_string());
+                    println!("{:>13} : {}", k, v);
                 }
             }
         }
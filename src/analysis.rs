@@ -1,24 +1,18 @@
 use super::*;
-use anyhow::{anyhow, Context, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::{collections::HashMap, ops::Add};
 
-#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Recommendation {
     StrongSell,
     Sell,
+    #[default]
     Neutral,
     Buy,
     StrongBuy,
 }
 
-impl Default for Recommendation {
-    fn default() -> Self {
-        Recommendation::Neutral
-    }
-}
-
 impl AsRef<str> for Recommendation {
     fn as_ref(&self) -> &str {
         match self {
@@ -38,7 +32,7 @@ impl std::fmt::Display for Recommendation {
 }
 
 impl FromStr for Recommendation {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -46,13 +40,13 @@ impl FromStr for Recommendation {
             "SELL" => Ok(Recommendation::Sell),
             "NEUTRAL" => Ok(Recommendation::Neutral),
             "BUY" => Ok(Recommendation::Buy),
-            "STRONG_BUF" => Ok(Recommendation::StrongBuy),
-            _ => Err(anyhow!("Invalid recommendation: {}", s)),
+            "STRONG_BUY" => Ok(Recommendation::StrongBuy),
+            _ => Err(Error::InvalidRecommendation(s.to_owned())),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Signal {
     RecommendOther,
     RecommendAll,
@@ -116,7 +110,7 @@ pub enum Signal {
     PivotMDemarkR1,
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct RecommendCounter {
     pub strong_sell: u32,
     pub sell: u32,
@@ -175,7 +169,7 @@ impl Add for RecommendCounter {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Analysis {
     pub recommend_summary: Recommendation,
     pub recommend_oscillators: Recommendation,
@@ -244,19 +238,18 @@ impl Analysis {
     }
 
     /// Retrieves symbol values for the given symbol from `tradingview` and computes technical analysis.
-    pub async fn get_technical_analysis<S1, S2>(
+    pub async fn get_technical_analysis<T, S>(
         tradingview: &TradingView,
-        symbol: S1,
-        interval: S2,
+        symbol: T,
+        interval: S,
     ) -> Result<Analysis>
     where
-        S1: AsRef<str>,
-        S2: AsRef<str>,
+        T: ToTicker,
+        S: AsRef<str>,
     {
         let values = tradingview
             .get_symbol_fields(symbol, interval, Analysis::ta_fields())
-            .await
-            .context("get symbol fields error")?;
+            .await?;
         Ok(Analysis::compute(&values.get_f64_values()))
     }
 
@@ -466,12 +459,6 @@ impl Analysis {
         analysis
     }
 
-    /// Adds a signal to the analysis.
-    fn add_signal(&mut self, signal: Signal, recommend: Recommendation) {
-        self.counter_summary.increase1(recommend);
-        self.signals.insert(signal, recommend);
-    }
-
     /// Adds an oscillator signal to the analysis.
     fn add_oscillator_signal(&mut self, signal: Signal, recommend: Recommendation) {
         self.counter_oscillators.increase1(recommend);
@@ -572,42 +559,16 @@ impl Analysis {
         }
     }
 
-    fn compute_bbbuy_signal(close: f64, bblower: f64) -> Recommendation {
-        if close < bblower {
-            Recommendation::Buy
-        } else {
-            Recommendation::Neutral
-        }
-    }
-
-    fn compute_bbsell_signal(close: f64, bbupper: f64) -> Recommendation {
-        if close > bbupper {
-            Recommendation::Sell
-        } else {
-            Recommendation::Neutral
-        }
-    }
-
-    fn compute_psar_signal(psar: f64, open: f64) -> Recommendation {
-        if psar < open {
-            Recommendation::Buy
-        } else if psar > open {
-            Recommendation::Sell
-        } else {
-            Recommendation::Neutral
-        }
-    }
-
     fn compute_recommend_signal(signal: f64) -> Recommendation {
-        if signal >= -1. && signal < -0.5 {
+        if (-1. ..-0.5).contains(&signal) {
             Recommendation::StrongSell
-        } else if signal >= -0.5 && signal < -0.1 {
+        } else if (-0.5..-0.1).contains(&signal) {
             Recommendation::Sell
-        } else if signal >= -0.1 && signal <= 0.1 {
+        } else if (-0.1..=0.1).contains(&signal) {
             Recommendation::Neutral
-        } else if signal > 0.1 && signal <= 0.5 {
+        } else if (0.1..=0.5).contains(&signal) {
             Recommendation::Buy
-        } else if signal > 0.5 && signal <= 1. {
+        } else if (0.5..=1.).contains(&signal) {
             Recommendation::StrongBuy
         } else {
             Recommendation::default()
@@ -671,10 +632,38 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockScanner;
+    use anyhow::{Context, Result};
+
+    #[test]
+    fn test_recommendation_from_str() {
+        let recommendations = [
+            Recommendation::StrongSell,
+            Recommendation::Sell,
+            Recommendation::Neutral,
+            Recommendation::Buy,
+            Recommendation::StrongBuy,
+        ];
+        for recommendation in recommendations {
+            assert_eq!(
+                recommendation.as_ref().parse::<Recommendation>().ok(),
+                Some(recommendation)
+            );
+        }
+        assert_eq!(
+            "STRONG_BUY".parse::<Recommendation>().ok(),
+            Some(Recommendation::StrongBuy)
+        );
+        assert!(matches!(
+            "STRONG_BUF".parse::<Recommendation>(),
+            Err(Error::InvalidRecommendation(_))
+        ));
+    }
 
     #[tokio::test]
     async fn test_analysis_compute() -> Result<()> {
-        let tradingview = TradingView::new(Screener::Crypto, "OKX");
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
         let symbol = "BTCUSDT.P";
         let interval = Interval::Hour1;
         let analysis = Analysis::get_technical_analysis(&tradingview, &symbol, &interval)
@@ -682,6 +671,9 @@ mod tests {
             .context("get technical analysis error")?;
 
         assert!(analysis.counter_summary.count() > 0);
+
+        let json = serde_json::to_string(&analysis)?;
+        assert_eq!(serde_json::from_str::<Analysis>(&json)?, analysis);
         Ok(())
     }
 }
@@ -0,0 +1,212 @@
+//! A blocking TradingView client, for programs that don't run a tokio runtime.
+//!
+//! The client runs the async client on its own runtime. Like `reqwest::blocking`,
+//! it must not be used from within an async runtime.
+use crate::{
+    Analysis, Field, FieldInfo, FieldWithInterval, Result, ScanPage, ScanQuery, ScanRow,
+    SimpleSymbolValues, SymbolInfo, TimedSymbolValues, ToTicker,
+};
+use futures::StreamExt;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking wrapper of `easytradeview::TradingView`, with the same methods.
+#[derive(Debug, Clone)]
+pub struct TradingView {
+    inner: crate::TradingView,
+    runtime: Arc<Runtime>,
+}
+
+impl TradingView {
+    /// Creates a new TradingView instance.
+    /// `exchange` is the default exchange, used for symbols given without one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created, use `from_async` to handle the error.
+    pub fn new<S1, S2>(screener: S1, exchange: S2) -> TradingView
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        Self::from_async(crate::TradingView::new(screener, exchange))
+            .expect("failed to create the runtime")
+    }
+
+    /// Creates a blocking client from an async one, e.g. configured with `easytradeview::TradingView::builder()`.
+    pub fn from_async(inner: crate::TradingView) -> Result<TradingView> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        Ok(TradingView {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Get the async client.
+    pub fn as_async(&self) -> &crate::TradingView {
+        &self.inner
+    }
+
+    /// Retrieves the specified fields for a given symbol, with all fields sharing the same interval.
+    pub fn get_symbol_fields<T, S>(
+        &self,
+        symbol: T,
+        interval: S,
+        fields: &[Field],
+    ) -> Result<SimpleSymbolValues>
+    where
+        T: ToTicker,
+        S: AsRef<str>,
+    {
+        self.runtime
+            .block_on(self.inner.get_symbol_fields(symbol, interval, fields))
+    }
+
+    /// Retrieves the specified fields for a given symbol, each field with its own interval.
+    pub fn get_symbol_fields_with_interval<T>(
+        &self,
+        symbol: T,
+        fields: &[FieldWithInterval],
+    ) -> Result<TimedSymbolValues>
+    where
+        T: ToTicker,
+    {
+        self.runtime
+            .block_on(self.inner.get_symbol_fields_with_interval(symbol, fields))
+    }
+
+    /// Searches for symbols by given exchanges, types and name. Retrieves basic fields along with specified extra fields.
+    pub fn search_symbols<S1, S2, S3>(
+        &self,
+        exchanges: &[S1],
+        types: &[S2],
+        name_filter: S3,
+        extra_fields: &[FieldWithInterval],
+    ) -> Result<Vec<TimedSymbolValues>>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+        S3: AsRef<str>,
+    {
+        self.runtime.block_on(self.inner.search_symbols(
+            exchanges,
+            types,
+            name_filter,
+            extra_fields,
+        ))
+    }
+
+    /// Retrieves the specified fields for the given symbols, each field with its own interval.
+    pub fn scan_symbols<T>(
+        &self,
+        symbols: &[T],
+        fields: &[FieldWithInterval],
+    ) -> Result<Vec<TimedSymbolValues>>
+    where
+        T: ToTicker,
+    {
+        self.runtime
+            .block_on(self.inner.scan_symbols(symbols, fields))
+    }
+
+    /// Retrieves the columns of `R` for the given symbols and decodes each symbol into a row, in the given order.
+    pub fn scan_as<R, T>(&self, symbols: &[T]) -> Result<Vec<R>>
+    where
+        R: ScanRow,
+        T: ToTicker,
+    {
+        self.runtime.block_on(self.inner.scan_as(symbols))
+    }
+
+    /// Retrieves the specified fields for the given symbols, returning one result per symbol in the given order.
+    pub fn scan_symbols_batch<T>(
+        &self,
+        symbols: &[T],
+        fields: &[FieldWithInterval],
+    ) -> Result<Vec<Result<TimedSymbolValues>>>
+    where
+        T: ToTicker,
+    {
+        self.runtime
+            .block_on(self.inner.scan_symbols_batch(symbols, fields))
+    }
+
+    /// Checks that a symbol exists on the screener and retrieves its canonical metadata.
+    pub fn resolve<T: ToTicker>(&self, symbol: T) -> Result<SymbolInfo> {
+        self.runtime.block_on(self.inner.resolve(symbol))
+    }
+
+    /// Runs a scan query on the screener, returning one page of results along with the total count.
+    pub fn scan(&self, query: &ScanQuery) -> Result<ScanPage> {
+        self.runtime.block_on(self.inner.scan(query))
+    }
+
+    /// Retrieves the catalog of the columns supported by a screener, e.g. `crypto` or `america`.
+    pub fn metainfo<S: AsRef<str>>(&self, screener: S) -> Result<Vec<FieldInfo>> {
+        self.runtime.block_on(self.inner.metainfo(screener))
+    }
+
+    /// Retrieves symbol values for the given symbol and computes technical analysis.
+    pub fn get_technical_analysis<T, S>(&self, symbol: T, interval: S) -> Result<Analysis>
+    where
+        T: ToTicker,
+        S: AsRef<str>,
+    {
+        self.runtime.block_on(Analysis::get_technical_analysis(
+            &self.inner,
+            symbol,
+            interval,
+        ))
+    }
+
+    /// Runs a scan query page by page, yielding every matching symbol until the total count is exhausted.
+    /// Each page is requested when the previous one has been consumed. A `page_size` of 0 is treated as 1.
+    pub fn scan_iter(
+        &self,
+        query: ScanQuery,
+        page_size: usize,
+    ) -> impl Iterator<Item = Result<TimedSymbolValues>> + '_ {
+        let mut stream = Box::pin(self.inner.scan_stream(query, page_size));
+        std::iter::from_fn(move || self.runtime.block_on(stream.next()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockScanner;
+    use crate::{Interval, Order, Screener, SymbolValues};
+    use anyhow::Result;
+
+    #[test]
+    fn test_blocking() -> Result<()> {
+        // The mock scanner runs on its own runtime, the blocking client on another one.
+        let runtime = Runtime::new()?;
+        let mock = runtime.block_on(MockScanner::start())?;
+        let tradingview = TradingView::from_async(mock.tradingview(Screener::Crypto, "OKX"))?;
+
+        let values = tradingview.get_symbol_fields("BTCUSDT.P", "60", &[Field::Close])?;
+        assert_eq!(values.values().len(), 1);
+
+        let analysis = tradingview.get_technical_analysis("BTCUSDT.P", "60")?;
+        assert!(analysis.counter_summary.count() > 0);
+
+        let close = Field::Close.with_interval(&Interval::Day1);
+        let data =
+            tradingview.scan_symbols(&["BTCUSDT", "ETHUSDT"], std::slice::from_ref(&close))?;
+        assert_eq!(data.len(), 2);
+
+        let query = ScanQuery::new(std::slice::from_ref(&close))
+            .sort_by(close, Order::Desc)
+            .range(0, 12);
+        let symbols: Vec<TimedSymbolValues> = tradingview
+            .scan_iter(query, 5)
+            .collect::<Result<_, crate::Error>>()?;
+        assert_eq!(symbols.len(), 12);
+        assert_eq!(mock.request_count(), 6);
+        Ok(())
+    }
+}
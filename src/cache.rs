@@ -0,0 +1,166 @@
+use super::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Number of values below which inserts don't bother purging expired values.
+const MIN_PURGE_LEN: usize = 64;
+
+/// An in-memory cache of symbol values, keyed by ticker and field with interval.
+/// Each entry expires after the TTL of its interval, so that e.g. 1m values can expire faster than 1d values.
+/// Expired entries are purged by inserts, whenever the cache has doubled in size since the last purge.
+#[derive(Debug)]
+pub struct ResponseCache {
+    default_ttl: Duration,
+    ttls: HashMap<Interval, Duration>,
+    entries: Mutex<Entries>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct Entries {
+    values: HashMap<(Ticker, FieldWithInterval), (Value, Instant)>,
+    /// The number of values at which the next insert purges the expired ones.
+    purge_len: usize,
+}
+
+impl Entries {
+    fn purge(&mut self, now: Instant) {
+        self.values.retain(|_, (_, expires_at)| *expires_at > now);
+        self.purge_len = (self.values.len() * 2).max(MIN_PURGE_LEN);
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+impl ResponseCache {
+    /// Creates a new `ResponseCache` instance, with the TTL used for intervals without their own TTL.
+    pub fn new(default_ttl: Duration) -> Self {
+        Self {
+            default_ttl,
+            ttls: HashMap::new(),
+            entries: Mutex::new(Entries {
+                values: HashMap::new(),
+                purge_len: MIN_PURGE_LEN,
+            }),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the TTL of the values of an interval.
+    pub fn ttl(mut self, interval: Interval, ttl: Duration) -> Self {
+        self.ttls.insert(interval, ttl);
+        self
+    }
+
+    /// Get the TTL of the values of an interval.
+    pub fn ttl_for(&self, interval: &Interval) -> Duration {
+        self.ttls.get(interval).copied().unwrap_or(self.default_ttl)
+    }
+
+    /// Get a value if it is cached and not expired, counting a hit or a miss.
+    pub fn get(&self, ticker: &Ticker, field: &FieldWithInterval) -> Option<Value> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = (ticker.clone(), field.clone());
+        let value = match entries.values.get(&key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.values.remove(&key);
+                None
+            }
+            None => None,
+        };
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::SeqCst);
+        value
+    }
+
+    /// Caches a value for the TTL of its interval.
+    /// Purges the expired values first if the cache has grown enough since the last purge.
+    pub fn insert(&self, ticker: &Ticker, field: &FieldWithInterval, value: Value) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.values.len() >= entries.purge_len {
+            entries.purge(now);
+        }
+        let expires_at = now + self.ttl_for(&field.interval);
+        entries
+            .values
+            .insert((ticker.clone(), field.clone()), (value, expires_at));
+    }
+
+    /// Get the number of lookups that found a value.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of lookups that found no value, or an expired one.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of cached values, including expired ones not purged yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().values.len()
+    }
+
+    /// Checks if the cache has no value.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all values, keeping the counters.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_ttl() {
+        let cache = ResponseCache::new(Duration::from_secs(60)).ttl(Interval::Min1, Duration::ZERO);
+        let ticker = Ticker::new("OKX", "BTCUSDT");
+        let close_1m = Field::Close.with_interval(&Interval::Min1);
+        let close_1d = Field::Close.with_interval(&Interval::Day1);
+        cache.insert(&ticker, &close_1m, Value::from(1.0));
+        cache.insert(&ticker, &close_1d, Value::from(2.0));
+
+        assert_eq!(cache.get(&ticker, &close_1m), None);
+        assert_eq!(cache.get(&ticker, &close_1d), Some(Value::from(2.0)));
+        assert_eq!(cache.get(&Ticker::new("BYBIT", "BTCUSDT"), &close_1d), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_purge() {
+        let cache = ResponseCache::new(Duration::ZERO).ttl(Interval::Day1, Duration::from_secs(60));
+        let close_1m = Field::Close.with_interval(&Interval::Min1);
+        let close_1d = Field::Close.with_interval(&Interval::Day1);
+        for i in 0..1000 {
+            let ticker = Ticker::new("OKX", format!("SYM{}", i));
+            cache.insert(&ticker, &close_1m, Value::from(i));
+        }
+        assert!(cache.len() <= MIN_PURGE_LEN);
+
+        for i in 0..1000 {
+            let ticker = Ticker::new("OKX", format!("SYM{}", i));
+            cache.insert(&ticker, &close_1d, Value::from(i));
+        }
+        assert!(cache.len() >= 1000 && cache.len() <= 1000 + MIN_PURGE_LEN);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{Error, Result};
+use reqwest::{Method, Url};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CassetteMode {
+    /// Sends requests to the scanner and records each response, written to the cassette file by `Cassette::save`
+    /// or when the cassette is dropped.
+    Record,
+    /// Serves responses from the cassette file, without sending any request.
+    Replay,
+}
+
+/// A JSON file of request/response pairs, used to capture scanner traffic once and replay it deterministically.
+/// Requests are matched on their method, path, query parameters and body, regardless of the scanner host
+/// and of the order of query parameters and fields.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    interactions: Mutex<BTreeMap<String, (Value, Value)>>,
+    /// Whether interactions were recorded since the file was last written.
+    unsaved: AtomicBool,
+}
+
+impl Cassette {
+    /// Creates a cassette recording to `path`, replacing any existing file once saved.
+    pub fn record<P: AsRef<Path>>(path: P) -> Cassette {
+        Self {
+            path: path.as_ref().to_owned(),
+            mode: CassetteMode::Record,
+            interactions: Mutex::new(BTreeMap::new()),
+            unsaved: AtomicBool::new(false),
+        }
+    }
+
+    /// Loads a cassette from `path` to replay its responses.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Cassette> {
+        let json: Value = serde_json::from_slice(&std::fs::read(path.as_ref())?)?;
+        let mut interactions = BTreeMap::new();
+        let data_array: Vec<Value> = vec![];
+        for data in json["interactions"].as_array().unwrap_or(&data_array) {
+            let request = data["request"].clone();
+            interactions.insert(Self::key(&request), (request, data["response"].clone()));
+        }
+        Ok(Self {
+            path: path.as_ref().to_owned(),
+            mode: CassetteMode::Replay,
+            interactions: Mutex::new(interactions),
+            unsaved: AtomicBool::new(false),
+        })
+    }
+
+    /// Get the mode of the cassette.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Get the path of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the number of recorded interactions.
+    pub fn len(&self) -> usize {
+        self.interactions.lock().unwrap().len()
+    }
+
+    /// Checks if the cassette has no recorded interaction.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the normalized JSON representation of a request to `url`, relative to the scanner url `base`.
+    pub(crate) fn request(method: &Method, base: &Url, url: &Url, body: Option<&Value>) -> Value {
+        let path = url.path().strip_prefix(base.path()).unwrap_or(url.path());
+        let query: BTreeMap<String, String> = url
+            .query_pairs()
+            .map(|(k, v)| {
+                let v = if k == "fields" {
+                    let mut fields: Vec<&str> = v.split(',').collect();
+                    fields.sort_unstable();
+                    fields.join(",")
+                } else {
+                    v.into_owned()
+                };
+                (k.into_owned(), v)
+            })
+            .collect();
+        json!({
+            "method": method.as_str(),
+            "path": path,
+            "query": query,
+            "body": body,
+        })
+    }
+
+    fn key(request: &Value) -> String {
+        // Object keys are sorted, so equal requests always serialize to the same string.
+        request.to_string()
+    }
+
+    /// Get the recorded response to a request.
+    pub(crate) fn get(&self, request: &Value) -> Result<Value> {
+        self.interactions
+            .lock()
+            .unwrap()
+            .get(&Self::key(request))
+            .map(|(_, response)| response.clone())
+            .ok_or_else(|| Error::CassetteMiss(Self::key(request)))
+    }
+
+    /// Records the response to a request, to be written by `save`.
+    pub(crate) fn insert(&self, request: Value, response: Value) {
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.insert(Self::key(&request), (request, response));
+        self.unsaved.store(true, Ordering::SeqCst);
+    }
+
+    /// Writes the recorded interactions to the cassette file, blocking until done.
+    pub fn save(&self) -> Result<()> {
+        let json = {
+            let interactions = self.interactions.lock().unwrap();
+            let interactions: Vec<Value> = interactions
+                .values()
+                .map(|(request, response)| json!({ "request": request, "response": response }))
+                .collect();
+            self.unsaved.store(false, Ordering::SeqCst);
+            json!({ "interactions": interactions })
+        };
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&json)?)?;
+        Ok(())
+    }
+}
+
+/// Saves the interactions recorded since the last `save`, ignoring errors.
+impl Drop for Cassette {
+    fn drop(&mut self) {
+        if self.unsaved.load(Ordering::SeqCst) {
+            let _ = self.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cassette_request() {
+        let base = Url::parse("http://127.0.0.1:8080/scanner/").unwrap();
+        let a =
+            Url::parse("http://127.0.0.1:8080/scanner/symbol?symbol=OKX:BTCUSDT&fields=close,RSI")
+                .unwrap();
+        let b =
+            Url::parse("http://127.0.0.1:9090/scanner/symbol?fields=RSI,close&symbol=OKX:BTCUSDT")
+                .unwrap();
+        let other =
+            Url::parse("http://127.0.0.1:8080/scanner/symbol?symbol=OKX:ETHUSDT&fields=close,RSI")
+                .unwrap();
+        let base_b = Url::parse("http://127.0.0.1:9090/scanner/").unwrap();
+
+        let request = Cassette::request(&Method::GET, &base, &a, None);
+        assert_eq!(request["path"], "symbol");
+        assert_eq!(
+            Cassette::key(&request),
+            Cassette::key(&Cassette::request(&Method::GET, &base_b, &b, None))
+        );
+        assert_ne!(
+            Cassette::key(&request),
+            Cassette::key(&Cassette::request(&Method::GET, &base, &other, None))
+        );
+    }
+
+    #[test]
+    fn test_cassette_save_on_drop() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("easytradeview-drop-{}.json", std::process::id()));
+        let base = Url::parse("http://127.0.0.1:8080/scanner/").unwrap();
+        let url = base.join("symbol?symbol=OKX:BTCUSDT&fields=close").unwrap();
+        let request = Cassette::request(&Method::GET, &base, &url, None);
+
+        let cassette = Cassette::record(&path);
+        cassette.insert(request.clone(), json!({"close": 1.5}));
+        assert!(!path.exists());
+        drop(cassette);
+
+        let cassette = Cassette::replay(&path)?;
+        assert_eq!(cassette.get(&request)?, json!({"close": 1.5}));
+        drop(cassette);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The error type of this crate.
+/// It is cheap to clone, so that the result of a request can be shared by coalesced callers.
+/// Wrapped errors are not repeated in the message, but returned by `source()`.
+/// New variants may be added in minor releases.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The scanner responded with a non-success status.
+    #[error("HTTP status {status}: {body}")]
+    Http { status: u16, body: String },
+    /// The scanner kept responding with `429 Too Many Requests`.
+    #[error("Rate limited, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
+    /// The request could not be sent or the response could not be received.
+    #[error("Transport error")]
+    Transport(#[source] Arc<reqwest::Error>),
+    /// A custom transport could not send the request or receive the response.
+    /// `retryable` tells whether the failure is worth retrying, e.g. a refused connection.
+    #[error("Transport error")]
+    CustomTransport {
+        #[source]
+        source: Arc<dyn std::error::Error + Send + Sync>,
+        retryable: bool,
+    },
+    /// The response body is not the expected JSON.
+    #[error("Decode error")]
+    Decode(#[source] Arc<serde_json::Error>),
+    #[error("Invalid url")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("Invalid field: {0}")]
+    InvalidField(String),
+    #[error("Invalid interval: {0}")]
+    InvalidInterval(String),
+    #[error("Invalid screener: {0}")]
+    InvalidScreener(String),
+    #[error("Invalid ticker: {0}")]
+    InvalidTicker(String),
+    #[error("Invalid recommendation: {0}")]
+    InvalidRecommendation(String),
+    #[error("Invalid rate limit: {0}")]
+    InvalidRateLimit(String),
+    #[error("Invalid retry policy: {0}")]
+    InvalidRetryPolicy(String),
+    #[error("IO error")]
+    Io(#[source] Arc<std::io::Error>),
+    /// The request was not recorded in the cassette being replayed.
+    #[error("Request not found in cassette: {0}")]
+    CassetteMiss(String),
+    /// The scanner does not know the symbol.
+    #[error("Unknown symbol: {0}")]
+    UnknownSymbol(String),
+    /// The scanner knows the symbol, but has no value for any of the requested fields.
+    #[error("No data: {0}")]
+    NoData(String),
+    /// The symbol values have no column for the field, e.g. because it was not requested.
+    #[error("Missing field: {0}")]
+    MissingField(String),
+    /// The value of a field is not of the requested type.
+    #[error("Unexpected value of {field}, expected {expected}: {value}")]
+    UnexpectedValue {
+        field: String,
+        expected: &'static str,
+        value: String,
+    },
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Transport(Arc::new(err))
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Decode(Arc::new(err))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(Arc::new(err))
+    }
+}
+
+impl Error {
+    /// Creates an error of a custom transport, which the retry policy retries if `retryable` is set.
+    pub fn custom_transport<E>(err: E, retryable: bool) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Error::CustomTransport {
+            source: Arc::from(err.into()),
+            retryable,
+        }
+    }
+
+    /// Checks if the error is caused by the server or the network, rather than by the request itself.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Http { status, .. } => *status >= 500,
+            Error::RateLimited { .. } => true,
+            Error::Transport(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+            Error::CustomTransport { retryable, .. } => *retryable,
+            _ => false,
+        }
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_error_chain() {
+        let err = Error::custom_transport("invalid request", false);
+        assert_eq!(err.to_string(), "Transport error");
+        assert_eq!(
+            err.source().map(|x| x.to_string()),
+            Some("invalid request".to_owned())
+        );
+        assert_eq!(
+            format!("{:#}", anyhow::Error::from(err)),
+            "Transport error: invalid request"
+        );
+
+        let err = Error::from(url::Url::parse("not a url").unwrap_err());
+        assert_eq!(
+            format!("{:#}", anyhow::Error::from(err)),
+            "Invalid url: relative URL without a base"
+        );
+    }
+}
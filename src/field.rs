@@ -1,12 +1,13 @@
 use super::*;
-use anyhow::{anyhow, Result};
 use field_attr::FieldAttr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 pub use serde_json::Value;
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub enum Field {
     Undefined(String),
     UndefinedWithoutInterval(String),
+    // @generated begin: fields
     RecommendOther,
     RecommendAll,
     RecommendMA,
@@ -20,6 +21,7 @@ pub enum Field {
     MinMov,
     MinMove2,
     Fractional,
+    Currency,
     Open,
     Close,
     Volume,
@@ -179,14 +181,15 @@ pub enum Field {
     Candle3WhiteSoldiers,
     CandleTriStarBearish,
     CandleTriStarBullish,
+    // @generated end: fields
 }
 
 impl Field {
     /// Parses a field from a string.
     pub fn parse(s: &str) -> Result<Field> {
         FieldAttr::find_by_name(s)
-            .and_then(|x| Some(x.field.clone()))
-            .ok_or(anyhow!("Invalid field: {}", s))
+            .map(|x| x.field.clone())
+            .ok_or_else(|| Error::InvalidField(s.to_owned()))
     }
 
     /// Parses a field, returning an undefined field if it fails
@@ -197,7 +200,7 @@ impl Field {
         } else {
             Field::undefined
         };
-        let field = parts.get(0).map_or(undefined_func(""), |x| {
+        let field = parts.first().map_or(undefined_func(""), |x| {
             Field::parse(x).unwrap_or(undefined_func(x))
         });
         field
@@ -207,9 +210,9 @@ impl Field {
     pub fn parse_with_interval(s: &str) -> Result<(Field, Option<Interval>)> {
         let parts: Vec<&str> = s.split("|").collect();
         let field = parts
-            .get(0)
+            .first()
             .and_then(|x| Field::parse(x).ok())
-            .ok_or(anyhow!("parse field error"))?;
+            .ok_or_else(|| Error::InvalidField(s.to_owned()))?;
         let interval = parts.get(1).map(|x| Interval::parse_undefined(x));
         Ok((field, interval))
     }
@@ -217,7 +220,7 @@ impl Field {
     /// Parses a field with interval, returning an undefined field if it fails
     pub fn parse_undefined_with_interval(s: &str) -> (Field, Option<Interval>) {
         let parts: Vec<&str> = s.split("|").collect();
-        let field = parts.get(0).map_or(Field::undefined(""), |x| {
+        let field = parts.first().map_or(Field::undefined(""), |x| {
             Field::parse(x).unwrap_or(Field::undefined(x))
         });
         let interval = parts.get(1).map(|x| Interval::parse_undefined(x));
@@ -252,17 +255,29 @@ impl Field {
         }
     }
 
+    /// Checks if the field takes an interval, as marked in its `FieldAttr`.
+    /// Undefined fields take one unless created with `Field::undefined_without_interval`.
+    pub fn has_interval(&self) -> bool {
+        match self {
+            Field::Undefined(_) => true,
+            Field::UndefinedWithoutInterval(_) => false,
+            _ => FieldAttr::find(self).is_none_or(|x| x.has_interval),
+        }
+    }
+
+    /// Get the kind of the values of the field, as marked in its `FieldAttr`.
+    /// Undefined fields have no known kind.
+    pub fn kind(&self) -> Option<ValueKind> {
+        FieldAttr::find(self).map(|x| x.kind)
+    }
+
     /// Get the string representation of the field with interval.
+    /// Fields without interval are represented without suffix, whatever the interval.
     pub fn to_string_with_interval(&self, interval: &Interval) -> String {
-        match self {
-            Field::Undefined(x) => x.to_string() + &interval.as_field_suffix(),
-            Field::UndefinedWithoutInterval(x) => x.to_string(),
-            Field::Name => self.to_string(),
-            Field::Exchange => self.to_string(),
-            Field::Description => self.to_string(),
-            Field::SubType => self.to_string(),
-            Field::UpdateMode => self.to_string(),
-            _ => self.to_string() + &interval.as_field_suffix(),
+        if self.has_interval() {
+            self.to_string() + &interval.as_field_suffix()
+        } else {
+            self.to_string()
         }
     }
 
@@ -386,7 +401,7 @@ impl Field {
 
 impl AsRef<Field> for Field {
     fn as_ref(&self) -> &Field {
-        &self
+        self
     }
 }
 
@@ -395,17 +410,35 @@ impl AsRef<str> for Field {
         match self {
             Field::Undefined(x) => x,
             Field::UndefinedWithoutInterval(x) => x,
-            _ => FieldAttr::find(self)
-                .and_then(|x| Some(x.name))
-                .unwrap_or(""),
+            _ => FieldAttr::find(self).map(|x| x.name).unwrap_or(""),
         }
     }
 }
 
-impl ToString for Field {
-    fn to_string(&self) -> String {
+/// Serializes as the wire name, e.g. `Recommend.All`.
+/// Undefined fields without interval get a trailing `|`, e.g. `sector|`, to tell them from undefined fields.
+impl Serialize for Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Field::UndefinedWithoutInterval(x) => serializer.collect_str(&format_args!("{}|", x)),
+            _ => serializer.serialize_str(self.as_ref()),
+        }
+    }
+}
+
+/// Deserializes from the wire name, as an undefined field if it is unknown,
+/// or an undefined field without interval if it has a trailing `|`.
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Field::parse_undefined(&s))
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let s: &str = self.as_ref();
-        s.to_owned()
+        write!(f, "{}", s)
     }
 }
 
@@ -417,46 +450,66 @@ pub struct FieldWithInterval {
 
 impl FieldWithInterval {
     /// Create a new `FieldWithInterval` instance.
+    /// Fields without interval get the default interval, so that they compare equal whatever the given interval.
     pub fn new(field: Field, interval: Interval) -> Self {
+        let interval = if field.has_interval() {
+            interval
+        } else {
+            Interval::default()
+        };
         Self { field, interval }
     }
 
+    /// Create a new `FieldWithInterval` instance, returning an error if a field without interval is given
+    /// another interval than the default one.
+    pub fn try_new(field: Field, interval: Interval) -> Result<Self> {
+        if !field.has_interval() && interval != Interval::default() {
+            return Err(Error::InvalidInterval(format!(
+                "{} takes no interval, got {}",
+                field, interval
+            )));
+        }
+        Ok(Self { field, interval })
+    }
+
     /// Parses a string to create a `FieldWithInterval` instance without requiring the interval to be defined.
     /// This function assumes a default interval if none is provided in the input string.
+    /// Returns an error if the string gives an interval to a field without interval.
     pub fn parse(s: &str) -> Result<FieldWithInterval> {
         let (field, interval) = Field::parse_with_interval(s)?;
-        let interval = interval.unwrap_or_default();
-        Ok(Self { field, interval })
+        Self::try_new(field, interval.unwrap_or_default())
     }
 
     /// Parses a string to create a `FieldWithInterval` instance, using a default interval if none is specified in the input.
+    /// The default interval is ignored by fields without interval, but an interval in the string is an error.
     pub fn parse_with_default_interval(
         s: &str,
         default_interval: &Interval,
     ) -> Result<FieldWithInterval> {
-        let (field, interval) = Field::parse_with_interval(s)?;
-        let interval = interval.unwrap_or(default_interval.clone());
-        Ok(Self { field, interval })
+        match Field::parse_with_interval(s)? {
+            (field, Some(interval)) => Self::try_new(field, interval),
+            (field, None) => Ok(Self::new(field, default_interval.clone())),
+        }
     }
 
     /// Parses a string to create a `FieldWithInterval` instance without requiring the interval to be defined.
     /// If the field is not found, it will be set to an undefined field.
     /// If the interval is not specified in the input string, it will be set to `Interval::default()`.
+    /// An interval given to a field without interval is dropped.
     pub fn parse_undefined(s: &str) -> FieldWithInterval {
         let (field, interval) = Field::parse_undefined_with_interval(s);
-        let interval = interval.unwrap_or_default();
-        Self { field, interval }
+        Self::new(field, interval.unwrap_or_default())
     }
 
     /// Parses a string to create a `FieldWithInterval` instance, using a default interval if none is specified in the input.
     /// If the field is not found, it will be set to an undefined field.
+    /// An interval given to a field without interval is dropped.
     pub fn parse_undefined_with_default_interval(
         s: &str,
         default_interval: &Interval,
     ) -> FieldWithInterval {
         let (field, interval) = Field::parse_undefined_with_interval(s);
-        let interval = interval.unwrap_or(default_interval.clone());
-        Self { field, interval }
+        Self::new(field, interval.unwrap_or(default_interval.clone()))
     }
 
     /// Get the reference to the field.
@@ -482,9 +535,30 @@ impl From<&str> for FieldWithInterval {
     }
 }
 
-impl ToString for FieldWithInterval {
-    fn to_string(&self) -> String {
-        self.field.to_string_with_interval(&self.interval)
+impl std::fmt::Display for FieldWithInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.field.to_string_with_interval(&self.interval))
+    }
+}
+
+/// Serializes as the wire name with interval, e.g. `close|60`.
+/// Undefined fields without interval are serialized like `Field`, e.g. `sector|`.
+impl Serialize for FieldWithInterval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.field {
+            Field::UndefinedWithoutInterval(_) => self.field.serialize(serializer),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldWithInterval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.strip_suffix('|') {
+            Some(_) => Ok(Self::new(Field::parse_undefined(&s), Interval::default())),
+            None => Ok(FieldWithInterval::parse_undefined(&s)),
+        }
     }
 }
 
@@ -565,4 +639,97 @@ mod tests {
             FieldWithInterval::new(Field::undefined("undefined"), Interval::undefined("5h"));
         assert_eq!(undefined_field.to_string(), "undefined|5h");
     }
+
+    #[test]
+    fn test_field_without_interval() {
+        for attr in FieldAttr::all_field_attrs() {
+            let field = attr.field.clone().with_interval(&Interval::Hour1);
+            let expected = if attr.has_interval {
+                format!("{}|60", attr.name)
+            } else {
+                attr.name.to_owned()
+            };
+            assert_eq!(field.to_string(), expected);
+        }
+
+        let type_1h = Field::Type.with_interval(&Interval::Hour1);
+        assert_eq!(type_1h, Field::Type.with_interval(&Interval::Day1));
+        assert_eq!(FieldWithInterval::parse_undefined("type|60"), type_1h);
+        assert_eq!(
+            FieldWithInterval::parse_with_default_interval("pricescale", &Interval::Hour1).ok(),
+            Some(Field::PriceScale.with_interval(&Interval::Day1))
+        );
+        assert!(matches!(
+            FieldWithInterval::parse("type|60"),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert!(FieldWithInterval::try_new(Field::Type, Interval::Hour1).is_err());
+        assert!(FieldWithInterval::try_new(Field::Close, Interval::Hour1).is_ok());
+    }
+
+    #[test]
+    fn test_serde_field_with_interval() -> Result<(), serde_json::Error> {
+        let fields = vec![
+            Field::RecommendAll.with_interval(&Interval::Hour1),
+            Field::Close.with_interval(&Interval::Min30),
+            Field::Type.with_interval(&Interval::default()),
+            Field::undefined("undefined").with_interval(&Interval::undefined("5h")),
+        ];
+        let json = serde_json::to_string(&fields)?;
+        assert_eq!(
+            json,
+            r#"["Recommend.All|60","close|30","type","undefined|5h"]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<FieldWithInterval>>(&json)?,
+            fields
+        );
+
+        assert_eq!(serde_json::to_string(&Field::RSI1)?, r#""RSI[1]""#);
+        assert_eq!(serde_json::from_str::<Field>(r#""RSI[1]""#)?, Field::RSI1);
+        for interval in Interval::all_intervals() {
+            let json = serde_json::to_string(interval)?;
+            assert_eq!(&serde_json::from_str::<Interval>(&json)?, interval);
+            let display = format!("{}", interval);
+            assert_eq!(&Interval::parse_undefined(&display), interval);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_round_trip() -> Result<(), serde_json::Error> {
+        let fields = FieldAttr::all_field_attrs()
+            .iter()
+            .map(|x| x.field.clone())
+            .chain([
+                Field::undefined("sector"),
+                Field::undefined_without_interval("sector"),
+            ]);
+        for field in fields {
+            let json = serde_json::to_string(&field)?;
+            assert_eq!(serde_json::from_str::<Field>(&json)?, field, "{}", json);
+
+            let intervals = Interval::all_intervals()
+                .iter()
+                .cloned()
+                .chain([Interval::undefined("5h")]);
+            for interval in intervals {
+                let field = field.clone().with_interval(&interval);
+                let json = serde_json::to_string(&field)?;
+                assert_eq!(
+                    serde_json::from_str::<FieldWithInterval>(&json)?,
+                    field,
+                    "{}",
+                    json
+                );
+            }
+        }
+        assert_eq!(
+            serde_json::to_string(
+                &Field::undefined_without_interval("sector").with_interval(&Interval::Hour1)
+            )?,
+            r#""sector|""#
+        );
+        Ok(())
+    }
 }
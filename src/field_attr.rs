@@ -3,18 +3,35 @@ use std::collections::HashMap;
 
 use crate::field::*;
 
+/// The kind of the values of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Number,
+    Integer,
+    Percent,
+    Price,
+    Text,
+    /// Text taking one of a fixed set of values, e.g. `spot` or `swap` for `type`.
+    Enum,
+    /// Unix time in seconds.
+    Timestamp,
+    Bool,
+}
+
 pub struct FieldAttr {
     pub field: Field,
     pub name: &'static str,
     pub has_interval: bool,
+    pub kind: ValueKind,
 }
 
 impl FieldAttr {
-    pub fn new(field: Field, name: &'static str, has_interval: bool) -> FieldAttr {
+    pub fn new(field: Field, name: &'static str, has_interval: bool, kind: ValueKind) -> FieldAttr {
         FieldAttr {
             field,
             name,
             has_interval,
+            kind,
         }
     }
 
@@ -29,7 +46,7 @@ impl FieldAttr {
                 .map(|x| (x.field.clone(), x))
                 .collect();
         };
-        MAP.get(field).map(|x| *x)
+        MAP.get(field).copied()
     }
 
     pub fn find_by_name(name: &str) -> Option<&'static FieldAttr> {
@@ -37,227 +54,186 @@ impl FieldAttr {
             static ref MAP: HashMap<&'static str, &'static FieldAttr> =
                 ALL_FIELD_ATTRS.iter().map(|x| (x.name, x)).collect();
         };
-        MAP.get(name).map(|x| *x)
+        MAP.get(name).copied()
     }
 }
 
 lazy_static! {
     static ref ALL_FIELD_ATTRS: Vec<FieldAttr> = vec![
-        FieldAttr::new(Field::RecommendOther, "Recommend.Other", true),
-        FieldAttr::new(Field::RecommendAll, "Recommend.All", true),
-        FieldAttr::new(Field::RecommendMA, "Recommend.MA", true),
-        FieldAttr::new(Field::Name, "name", false),
-        FieldAttr::new(Field::Exchange, "exchange", false),
-        FieldAttr::new(Field::Description, "description", false),
-        FieldAttr::new(Field::Type, "type", false),
-        FieldAttr::new(Field::SubType, "subtype", false),
-        FieldAttr::new(Field::UpdateMode, "update_mode", false),
-        FieldAttr::new(Field::PriceScale, "pricescale", false),
-        FieldAttr::new(Field::MinMov, "minmov", false),
-        FieldAttr::new(Field::MinMove2, "minmove2", false),
-        FieldAttr::new(Field::Fractional, "fractional", false),
-        FieldAttr::new(Field::Open, "open", true),
-        FieldAttr::new(Field::Close, "close", true),
-        FieldAttr::new(Field::Volume, "volume", true),
-        FieldAttr::new(Field::Change, "change", true),
-        FieldAttr::new(Field::ChangeAbs, "change_abs", true),
-        FieldAttr::new(Field::Gap, "gap", true),
-        FieldAttr::new(Field::HighW, "High.W", true),
-        FieldAttr::new(Field::LowW, "Low.W", true),
-        FieldAttr::new(Field::PerfW, "Perf.W", true),
-        FieldAttr::new(Field::High1M, "High.1M", true),
-        FieldAttr::new(Field::Low1M, "Low.1M", true),
-        FieldAttr::new(Field::Perf1M, "Perf.1M", true),
-        FieldAttr::new(Field::High3M, "High.3M", true),
-        FieldAttr::new(Field::Low3M, "Low.3M", true),
-        FieldAttr::new(Field::Perf3M, "Perf.3M", true),
-        FieldAttr::new(Field::High6M, "High.6M", true),
-        FieldAttr::new(Field::Low6M, "Low.6M", true),
-        FieldAttr::new(Field::Perf6M, "Perf.6M", true),
-        FieldAttr::new(Field::HighAll, "High.All", true),
-        FieldAttr::new(Field::LowAll, "Low.All", true),
-        FieldAttr::new(Field::RSI, "RSI", true),
-        FieldAttr::new(Field::RSI1, "RSI[1]", true),
-        FieldAttr::new(Field::RSI7, "RSI7", true),
-        FieldAttr::new(Field::StochK, "Stoch.K", true),
-        FieldAttr::new(Field::StochD, "Stoch.D", true),
-        FieldAttr::new(Field::StochK1, "Stoch.K[1]", true),
-        FieldAttr::new(Field::StochD1, "Stoch.D[1]", true),
-        FieldAttr::new(Field::CCI20, "CCI20", true),
-        FieldAttr::new(Field::CCI201, "CCI20[1]", true),
-        FieldAttr::new(Field::ADX, "ADX", true),
-        FieldAttr::new(Field::ADXplusDI, "ADX+DI", true),
-        FieldAttr::new(Field::ADXminusDI, "ADX-DI", true),
-        FieldAttr::new(Field::ADXplusDI1, "ADX+DI[1]", true),
-        FieldAttr::new(Field::ADXminusDI1, "ADX-DI[1]", true),
-        FieldAttr::new(Field::AO, "AO", true),
-        FieldAttr::new(Field::AO1, "AO[1]", true),
-        FieldAttr::new(Field::AO2, "AO[2]", true),
-        FieldAttr::new(Field::Mom, "Mom", true),
-        FieldAttr::new(Field::Mom1, "Mom[1]", true),
-        FieldAttr::new(Field::MACDmacd, "MACD.macd", true),
-        FieldAttr::new(Field::MACDsignal, "MACD.signal", true),
-        FieldAttr::new(Field::RecStochRSI, "Rec.Stoch.RSI", true),
-        FieldAttr::new(Field::StochRSIK, "Stoch.RSI.K", true),
-        FieldAttr::new(Field::StochRSID, "Stoch.RSI.D", true),
-        FieldAttr::new(Field::RecWR, "Rec.WR", true),
-        FieldAttr::new(Field::WR, "W.R", true),
-        FieldAttr::new(Field::RecBBPower, "Rec.BBPower", true),
-        FieldAttr::new(Field::BBPower, "BBPower", true),
-        FieldAttr::new(Field::RecUO, "Rec.UO", true),
-        FieldAttr::new(Field::UO, "UO", true),
-        FieldAttr::new(Field::EMA5, "EMA5", true),
-        FieldAttr::new(Field::SMA5, "SMA5", true),
-        FieldAttr::new(Field::EMA10, "EMA10", true),
-        FieldAttr::new(Field::SMA10, "SMA10", true),
-        FieldAttr::new(Field::EMA20, "EMA20", true),
-        FieldAttr::new(Field::SMA20, "SMA20", true),
-        FieldAttr::new(Field::EMA30, "EMA30", true),
-        FieldAttr::new(Field::SMA30, "SMA30", true),
-        FieldAttr::new(Field::EMA50, "EMA50", true),
-        FieldAttr::new(Field::SMA50, "SMA50", true),
-        FieldAttr::new(Field::EMA100, "EMA100", true),
-        FieldAttr::new(Field::SMA100, "SMA100", true),
-        FieldAttr::new(Field::EMA200, "EMA200", true),
-        FieldAttr::new(Field::SMA200, "SMA200", true),
-        FieldAttr::new(Field::RecIchimoku, "Rec.Ichimoku", true),
-        FieldAttr::new(Field::IchimokuBLine, "Ichimoku.BLine", true),
-        FieldAttr::new(Field::RecVWMA, "Rec.VWMA", true),
-        FieldAttr::new(Field::VWMA, "VWMA", true),
-        FieldAttr::new(Field::RecHullMA9, "Rec.HullMA9", true),
-        FieldAttr::new(Field::HullMA9, "HullMA9", true),
-        FieldAttr::new(Field::PivotMClassicS3, "Pivot.M.Classic.S3", true),
-        FieldAttr::new(Field::PivotMClassicS2, "Pivot.M.Classic.S2", true),
-        FieldAttr::new(Field::PivotMClassicS1, "Pivot.M.Classic.S1", true),
-        FieldAttr::new(Field::PivotMClassicMiddle, "Pivot.M.Classic.Middle", true),
-        FieldAttr::new(Field::PivotMClassicR1, "Pivot.M.Classic.R1", true),
-        FieldAttr::new(Field::PivotMClassicR2, "Pivot.M.Classic.R2", true),
-        FieldAttr::new(Field::PivotMClassicR3, "Pivot.M.Classic.R3", true),
-        FieldAttr::new(Field::PivotMFibonacciS3, "Pivot.M.Fibonacci.S3", true),
-        FieldAttr::new(Field::PivotMFibonacciS2, "Pivot.M.Fibonacci.S2", true),
-        FieldAttr::new(Field::PivotMFibonacciS1, "Pivot.M.Fibonacci.S1", true),
-        FieldAttr::new(
-            Field::PivotMFibonacciMiddle,
-            "Pivot.M.Fibonacci.Middle",
-            true
-        ),
-        FieldAttr::new(Field::PivotMFibonacciR1, "Pivot.M.Fibonacci.R1", true),
-        FieldAttr::new(Field::PivotMFibonacciR2, "Pivot.M.Fibonacci.R2", true),
-        FieldAttr::new(Field::PivotMFibonacciR3, "Pivot.M.Fibonacci.R3", true),
-        FieldAttr::new(Field::PivotMCamarillaS3, "Pivot.M.Camarilla.S3", true),
-        FieldAttr::new(Field::PivotMCamarillaS2, "Pivot.M.Camarilla.S2", true),
-        FieldAttr::new(Field::PivotMCamarillaS1, "Pivot.M.Camarilla.S1", true),
-        FieldAttr::new(
-            Field::PivotMCamarillaMiddle,
-            "Pivot.M.Camarilla.Middle",
-            true
-        ),
-        FieldAttr::new(Field::PivotMCamarillaR1, "Pivot.M.Camarilla.R1", true),
-        FieldAttr::new(Field::PivotMCamarillaR2, "Pivot.M.Camarilla.R2", true),
-        FieldAttr::new(Field::PivotMCamarillaR3, "Pivot.M.Camarilla.R3", true),
-        FieldAttr::new(Field::PivotMWoodieS3, "Pivot.M.Woodie.S3", true),
-        FieldAttr::new(Field::PivotMWoodieS2, "Pivot.M.Woodie.S2", true),
-        FieldAttr::new(Field::PivotMWoodieS1, "Pivot.M.Woodie.S1", true),
-        FieldAttr::new(Field::PivotMWoodieMiddle, "Pivot.M.Woodie.Middle", true),
-        FieldAttr::new(Field::PivotMWoodieR1, "Pivot.M.Woodie.R1", true),
-        FieldAttr::new(Field::PivotMWoodieR2, "Pivot.M.Woodie.R2", true),
-        FieldAttr::new(Field::PivotMWoodieR3, "Pivot.M.Woodie.R3", true),
-        FieldAttr::new(Field::PivotMDemarkS1, "Pivot.M.Demark.S1", true),
-        FieldAttr::new(Field::PivotMDemarkMiddle, "Pivot.M.Demark.Middle", true),
-        FieldAttr::new(Field::PivotMDemarkR1, "Pivot.M.Demark.R1", true),
-        FieldAttr::new(Field::PSAR, "P.SAR", true),
-        FieldAttr::new(Field::BBlower, "BB.lower", true),
-        FieldAttr::new(Field::BBupper, "BB.upper", true),
-        FieldAttr::new(Field::Price52WeekHigh, "price_52_week_high", true),
-        FieldAttr::new(Field::Price52WeekLow, "price_52_week_low", true),
-        FieldAttr::new(Field::AroonDown, "Aroon.Down", true),
-        FieldAttr::new(Field::AroonUp, "Aroon.Up", true),
-        FieldAttr::new(Field::ADR, "ADR", true),
-        FieldAttr::new(Field::ATR, "ATR", true),
-        FieldAttr::new(Field::AverageVolume10dCalc, "average_volume_10d_calc", true),
-        FieldAttr::new(Field::PerfY, "Perf.Y", true),
-        FieldAttr::new(Field::PerfYTD, "Perf.YTD", true),
-        FieldAttr::new(Field::AverageVolume30dCalc, "average_volume_30d_calc", true),
-        FieldAttr::new(Field::AverageVolume60dCalc, "average_volume_60d_calc", true),
-        FieldAttr::new(Field::AverageVolume90dCalc, "average_volume_90d_calc", true),
-        FieldAttr::new(Field::ChangeFromOpenAbs, "change_from_open_abs", true),
-        FieldAttr::new(Field::ChangeFromOpen, "change_from_open", true),
-        FieldAttr::new(Field::DonchCh20Lower, "DonchCh20.Lower", true),
-        FieldAttr::new(Field::DonchCh20Upper, "DonchCh20.Upper", true),
-        FieldAttr::new(Field::IchimokuCLine, "Ichimoku.CLine", true),
-        FieldAttr::new(Field::IchimokuLead1, "Ichimoku.Lead1", true),
-        FieldAttr::new(Field::IchimokuLead2, "Ichimoku.Lead2", true),
-        FieldAttr::new(Field::KltChnllower, "KltChnl.lower", true),
-        FieldAttr::new(Field::KltChnlupper, "KltChnl.upper", true),
-        FieldAttr::new(Field::MarketCapCalc, "market_cap_calc", true),
-        FieldAttr::new(Field::ROC, "ROC", true),
-        FieldAttr::new(
-            Field::RelativeVolume10dCalc,
-            "relative_volume_10d_calc",
-            true
-        ),
-        FieldAttr::new(Field::VolatilityD, "Volatility.D", true),
-        FieldAttr::new(Field::VolatilityM, "Volatility.M", true),
-        FieldAttr::new(Field::VolatilityW, "Volatility.W", true),
-        FieldAttr::new(Field::VWAP, "VWAP", true),
-        FieldAttr::new(
-            Field::CandleAbandonedBabyBearish,
-            "Candle.AbandonedBaby.Bearish",
-            true
-        ),
-        FieldAttr::new(
-            Field::CandleAbandonedBabyBullish,
-            "Candle.AbandonedBaby.Bullish",
-            true
-        ),
-        FieldAttr::new(
-            Field::CandleEngulfingBearish,
-            "Candle.Engulfing.Bearish",
-            true
-        ),
-        FieldAttr::new(Field::CandleHaramiBearish, "Candle.Harami.Bearish", true),
-        FieldAttr::new(
-            Field::CandleEngulfingBullish,
-            "Candle.Engulfing.Bullish",
-            true
-        ),
-        FieldAttr::new(Field::CandleHaramiBullish, "Candle.Harami.Bullish", true),
-        FieldAttr::new(Field::CandleDoji, "Candle.Doji", true),
-        FieldAttr::new(Field::CandleDojiDragonfly, "Candle.Doji.Dragonfly", true),
-        FieldAttr::new(Field::CandleEveningStar, "Candle.EveningStar", true),
-        FieldAttr::new(Field::CandleDojiGravestone, "Candle.Doji.Gravestone", true),
-        FieldAttr::new(Field::CandleHammer, "Candle.Hammer", true),
-        FieldAttr::new(Field::CandleHangingMan, "Candle.HangingMan", true),
-        FieldAttr::new(Field::CandleInvertedHammer, "Candle.InvertedHammer", true),
-        FieldAttr::new(Field::CandleKickingBearish, "Candle.Kicking.Bearish", true),
-        FieldAttr::new(Field::CandleKickingBullish, "Candle.Kicking.Bullish", true),
-        FieldAttr::new(
-            Field::CandleLongShadowLower,
-            "Candle.LongShadow.Lower",
-            true
-        ),
-        FieldAttr::new(
-            Field::CandleLongShadowUpper,
-            "Candle.LongShadow.Upper",
-            true
-        ),
-        FieldAttr::new(Field::CandleMarubozuBlack, "Candle.Marubozu.Black", true),
-        FieldAttr::new(Field::CandleMarubozuWhite, "Candle.Marubozu.White", true),
-        FieldAttr::new(Field::CandleMorningStar, "Candle.MorningStar", true),
-        FieldAttr::new(Field::CandleShootingStar, "Candle.ShootingStar", true),
-        FieldAttr::new(
-            Field::CandleSpinningTopBlack,
-            "Candle.SpinningTop.Black",
-            true
-        ),
-        FieldAttr::new(
-            Field::CandleSpinningTopWhite,
-            "Candle.SpinningTop.White",
-            true
-        ),
-        FieldAttr::new(Field::Candle3BlackCrows, "Candle.3BlackCrows", true),
-        FieldAttr::new(Field::Candle3WhiteSoldiers, "Candle.3WhiteSoldiers", true),
-        FieldAttr::new(Field::CandleTriStarBearish, "Candle.TriStar.Bearish", true),
-        FieldAttr::new(Field::CandleTriStarBullish, "Candle.TriStar.Bullish", true),
+        // @generated begin: field attrs
+        FieldAttr::new(Field::RecommendOther, "Recommend.Other", true, ValueKind::Number),
+        FieldAttr::new(Field::RecommendAll, "Recommend.All", true, ValueKind::Number),
+        FieldAttr::new(Field::RecommendMA, "Recommend.MA", true, ValueKind::Number),
+        FieldAttr::new(Field::Name, "name", false, ValueKind::Text),
+        FieldAttr::new(Field::Exchange, "exchange", false, ValueKind::Text),
+        FieldAttr::new(Field::Description, "description", false, ValueKind::Text),
+        FieldAttr::new(Field::Type, "type", false, ValueKind::Enum),
+        FieldAttr::new(Field::SubType, "subtype", false, ValueKind::Enum),
+        FieldAttr::new(Field::UpdateMode, "update_mode", false, ValueKind::Text),
+        FieldAttr::new(Field::PriceScale, "pricescale", false, ValueKind::Integer),
+        FieldAttr::new(Field::MinMov, "minmov", false, ValueKind::Integer),
+        FieldAttr::new(Field::MinMove2, "minmove2", false, ValueKind::Integer),
+        FieldAttr::new(Field::Fractional, "fractional", false, ValueKind::Bool),
+        FieldAttr::new(Field::Currency, "currency", false, ValueKind::Text),
+        FieldAttr::new(Field::Open, "open", true, ValueKind::Price),
+        FieldAttr::new(Field::Close, "close", true, ValueKind::Price),
+        FieldAttr::new(Field::Volume, "volume", true, ValueKind::Number),
+        FieldAttr::new(Field::Change, "change", true, ValueKind::Percent),
+        FieldAttr::new(Field::ChangeAbs, "change_abs", true, ValueKind::Price),
+        FieldAttr::new(Field::Gap, "gap", true, ValueKind::Percent),
+        FieldAttr::new(Field::HighW, "High.W", true, ValueKind::Price),
+        FieldAttr::new(Field::LowW, "Low.W", true, ValueKind::Price),
+        FieldAttr::new(Field::PerfW, "Perf.W", true, ValueKind::Percent),
+        FieldAttr::new(Field::High1M, "High.1M", true, ValueKind::Price),
+        FieldAttr::new(Field::Low1M, "Low.1M", true, ValueKind::Price),
+        FieldAttr::new(Field::Perf1M, "Perf.1M", true, ValueKind::Percent),
+        FieldAttr::new(Field::High3M, "High.3M", true, ValueKind::Price),
+        FieldAttr::new(Field::Low3M, "Low.3M", true, ValueKind::Price),
+        FieldAttr::new(Field::Perf3M, "Perf.3M", true, ValueKind::Percent),
+        FieldAttr::new(Field::High6M, "High.6M", true, ValueKind::Price),
+        FieldAttr::new(Field::Low6M, "Low.6M", true, ValueKind::Price),
+        FieldAttr::new(Field::Perf6M, "Perf.6M", true, ValueKind::Percent),
+        FieldAttr::new(Field::HighAll, "High.All", true, ValueKind::Price),
+        FieldAttr::new(Field::LowAll, "Low.All", true, ValueKind::Price),
+        FieldAttr::new(Field::RSI, "RSI", true, ValueKind::Number),
+        FieldAttr::new(Field::RSI1, "RSI[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::RSI7, "RSI7", true, ValueKind::Number),
+        FieldAttr::new(Field::StochK, "Stoch.K", true, ValueKind::Number),
+        FieldAttr::new(Field::StochD, "Stoch.D", true, ValueKind::Number),
+        FieldAttr::new(Field::StochK1, "Stoch.K[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::StochD1, "Stoch.D[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::CCI20, "CCI20", true, ValueKind::Number),
+        FieldAttr::new(Field::CCI201, "CCI20[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::ADX, "ADX", true, ValueKind::Number),
+        FieldAttr::new(Field::ADXplusDI, "ADX+DI", true, ValueKind::Number),
+        FieldAttr::new(Field::ADXminusDI, "ADX-DI", true, ValueKind::Number),
+        FieldAttr::new(Field::ADXplusDI1, "ADX+DI[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::ADXminusDI1, "ADX-DI[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::AO, "AO", true, ValueKind::Number),
+        FieldAttr::new(Field::AO1, "AO[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::AO2, "AO[2]", true, ValueKind::Number),
+        FieldAttr::new(Field::Mom, "Mom", true, ValueKind::Number),
+        FieldAttr::new(Field::Mom1, "Mom[1]", true, ValueKind::Number),
+        FieldAttr::new(Field::MACDmacd, "MACD.macd", true, ValueKind::Number),
+        FieldAttr::new(Field::MACDsignal, "MACD.signal", true, ValueKind::Number),
+        FieldAttr::new(Field::RecStochRSI, "Rec.Stoch.RSI", true, ValueKind::Number),
+        FieldAttr::new(Field::StochRSIK, "Stoch.RSI.K", true, ValueKind::Number),
+        FieldAttr::new(Field::StochRSID, "Stoch.RSI.D", true, ValueKind::Number),
+        FieldAttr::new(Field::RecWR, "Rec.WR", true, ValueKind::Number),
+        FieldAttr::new(Field::WR, "W.R", true, ValueKind::Number),
+        FieldAttr::new(Field::RecBBPower, "Rec.BBPower", true, ValueKind::Number),
+        FieldAttr::new(Field::BBPower, "BBPower", true, ValueKind::Number),
+        FieldAttr::new(Field::RecUO, "Rec.UO", true, ValueKind::Number),
+        FieldAttr::new(Field::UO, "UO", true, ValueKind::Number),
+        FieldAttr::new(Field::EMA5, "EMA5", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA5, "SMA5", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA10, "EMA10", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA10, "SMA10", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA20, "EMA20", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA20, "SMA20", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA30, "EMA30", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA30, "SMA30", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA50, "EMA50", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA50, "SMA50", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA100, "EMA100", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA100, "SMA100", true, ValueKind::Price),
+        FieldAttr::new(Field::EMA200, "EMA200", true, ValueKind::Price),
+        FieldAttr::new(Field::SMA200, "SMA200", true, ValueKind::Price),
+        FieldAttr::new(Field::RecIchimoku, "Rec.Ichimoku", true, ValueKind::Number),
+        FieldAttr::new(Field::IchimokuBLine, "Ichimoku.BLine", true, ValueKind::Price),
+        FieldAttr::new(Field::RecVWMA, "Rec.VWMA", true, ValueKind::Number),
+        FieldAttr::new(Field::VWMA, "VWMA", true, ValueKind::Price),
+        FieldAttr::new(Field::RecHullMA9, "Rec.HullMA9", true, ValueKind::Number),
+        FieldAttr::new(Field::HullMA9, "HullMA9", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicS3, "Pivot.M.Classic.S3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicS2, "Pivot.M.Classic.S2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicS1, "Pivot.M.Classic.S1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicMiddle, "Pivot.M.Classic.Middle", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicR1, "Pivot.M.Classic.R1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicR2, "Pivot.M.Classic.R2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMClassicR3, "Pivot.M.Classic.R3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciS3, "Pivot.M.Fibonacci.S3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciS2, "Pivot.M.Fibonacci.S2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciS1, "Pivot.M.Fibonacci.S1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciMiddle, "Pivot.M.Fibonacci.Middle", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciR1, "Pivot.M.Fibonacci.R1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciR2, "Pivot.M.Fibonacci.R2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMFibonacciR3, "Pivot.M.Fibonacci.R3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaS3, "Pivot.M.Camarilla.S3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaS2, "Pivot.M.Camarilla.S2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaS1, "Pivot.M.Camarilla.S1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaMiddle, "Pivot.M.Camarilla.Middle", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaR1, "Pivot.M.Camarilla.R1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaR2, "Pivot.M.Camarilla.R2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMCamarillaR3, "Pivot.M.Camarilla.R3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieS3, "Pivot.M.Woodie.S3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieS2, "Pivot.M.Woodie.S2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieS1, "Pivot.M.Woodie.S1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieMiddle, "Pivot.M.Woodie.Middle", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieR1, "Pivot.M.Woodie.R1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieR2, "Pivot.M.Woodie.R2", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMWoodieR3, "Pivot.M.Woodie.R3", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMDemarkS1, "Pivot.M.Demark.S1", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMDemarkMiddle, "Pivot.M.Demark.Middle", true, ValueKind::Price),
+        FieldAttr::new(Field::PivotMDemarkR1, "Pivot.M.Demark.R1", true, ValueKind::Price),
+        FieldAttr::new(Field::PSAR, "P.SAR", true, ValueKind::Price),
+        FieldAttr::new(Field::BBlower, "BB.lower", true, ValueKind::Price),
+        FieldAttr::new(Field::BBupper, "BB.upper", true, ValueKind::Price),
+        FieldAttr::new(Field::Price52WeekHigh, "price_52_week_high", true, ValueKind::Price),
+        FieldAttr::new(Field::Price52WeekLow, "price_52_week_low", true, ValueKind::Price),
+        FieldAttr::new(Field::AroonDown, "Aroon.Down", true, ValueKind::Number),
+        FieldAttr::new(Field::AroonUp, "Aroon.Up", true, ValueKind::Number),
+        FieldAttr::new(Field::ADR, "ADR", true, ValueKind::Number),
+        FieldAttr::new(Field::ATR, "ATR", true, ValueKind::Number),
+        FieldAttr::new(Field::AverageVolume10dCalc, "average_volume_10d_calc", true, ValueKind::Number),
+        FieldAttr::new(Field::PerfY, "Perf.Y", true, ValueKind::Percent),
+        FieldAttr::new(Field::PerfYTD, "Perf.YTD", true, ValueKind::Percent),
+        FieldAttr::new(Field::AverageVolume30dCalc, "average_volume_30d_calc", true, ValueKind::Number),
+        FieldAttr::new(Field::AverageVolume60dCalc, "average_volume_60d_calc", true, ValueKind::Number),
+        FieldAttr::new(Field::AverageVolume90dCalc, "average_volume_90d_calc", true, ValueKind::Number),
+        FieldAttr::new(Field::ChangeFromOpenAbs, "change_from_open_abs", true, ValueKind::Price),
+        FieldAttr::new(Field::ChangeFromOpen, "change_from_open", true, ValueKind::Percent),
+        FieldAttr::new(Field::DonchCh20Lower, "DonchCh20.Lower", true, ValueKind::Price),
+        FieldAttr::new(Field::DonchCh20Upper, "DonchCh20.Upper", true, ValueKind::Price),
+        FieldAttr::new(Field::IchimokuCLine, "Ichimoku.CLine", true, ValueKind::Price),
+        FieldAttr::new(Field::IchimokuLead1, "Ichimoku.Lead1", true, ValueKind::Price),
+        FieldAttr::new(Field::IchimokuLead2, "Ichimoku.Lead2", true, ValueKind::Price),
+        FieldAttr::new(Field::KltChnllower, "KltChnl.lower", true, ValueKind::Price),
+        FieldAttr::new(Field::KltChnlupper, "KltChnl.upper", true, ValueKind::Price),
+        FieldAttr::new(Field::MarketCapCalc, "market_cap_calc", true, ValueKind::Price),
+        FieldAttr::new(Field::ROC, "ROC", true, ValueKind::Number),
+        FieldAttr::new(Field::RelativeVolume10dCalc, "relative_volume_10d_calc", true, ValueKind::Number),
+        FieldAttr::new(Field::VolatilityD, "Volatility.D", true, ValueKind::Percent),
+        FieldAttr::new(Field::VolatilityM, "Volatility.M", true, ValueKind::Percent),
+        FieldAttr::new(Field::VolatilityW, "Volatility.W", true, ValueKind::Percent),
+        FieldAttr::new(Field::VWAP, "VWAP", true, ValueKind::Price),
+        FieldAttr::new(Field::CandleAbandonedBabyBearish, "Candle.AbandonedBaby.Bearish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleAbandonedBabyBullish, "Candle.AbandonedBaby.Bullish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleEngulfingBearish, "Candle.Engulfing.Bearish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleHaramiBearish, "Candle.Harami.Bearish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleEngulfingBullish, "Candle.Engulfing.Bullish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleHaramiBullish, "Candle.Harami.Bullish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleDoji, "Candle.Doji", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleDojiDragonfly, "Candle.Doji.Dragonfly", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleEveningStar, "Candle.EveningStar", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleDojiGravestone, "Candle.Doji.Gravestone", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleHammer, "Candle.Hammer", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleHangingMan, "Candle.HangingMan", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleInvertedHammer, "Candle.InvertedHammer", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleKickingBearish, "Candle.Kicking.Bearish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleKickingBullish, "Candle.Kicking.Bullish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleLongShadowLower, "Candle.LongShadow.Lower", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleLongShadowUpper, "Candle.LongShadow.Upper", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleMarubozuBlack, "Candle.Marubozu.Black", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleMarubozuWhite, "Candle.Marubozu.White", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleMorningStar, "Candle.MorningStar", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleShootingStar, "Candle.ShootingStar", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleSpinningTopBlack, "Candle.SpinningTop.Black", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleSpinningTopWhite, "Candle.SpinningTop.White", true, ValueKind::Number),
+        FieldAttr::new(Field::Candle3BlackCrows, "Candle.3BlackCrows", true, ValueKind::Number),
+        FieldAttr::new(Field::Candle3WhiteSoldiers, "Candle.3WhiteSoldiers", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleTriStarBearish, "Candle.TriStar.Bearish", true, ValueKind::Number),
+        FieldAttr::new(Field::CandleTriStarBullish, "Candle.TriStar.Bullish", true, ValueKind::Number),
+        // @generated end: field attrs
     ];
 }
@@ -0,0 +1,376 @@
+use super::*;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOperation {
+    Greater,
+    Less,
+    EGreater,
+    ELess,
+    InRange,
+    NotInRange,
+    Equal,
+    NEqual,
+    Crosses,
+    CrossesAbove,
+    CrossesBelow,
+    Match,
+    Has,
+    HasNoneOf,
+}
+
+impl AsRef<str> for FilterOperation {
+    fn as_ref(&self) -> &str {
+        match self {
+            FilterOperation::Greater => "greater",
+            FilterOperation::Less => "less",
+            FilterOperation::EGreater => "egreater",
+            FilterOperation::ELess => "eless",
+            FilterOperation::InRange => "in_range",
+            FilterOperation::NotInRange => "not_in_range",
+            FilterOperation::Equal => "equal",
+            FilterOperation::NEqual => "nequal",
+            FilterOperation::Crosses => "crosses",
+            FilterOperation::CrossesAbove => "crosses_above",
+            FilterOperation::CrossesBelow => "crosses_below",
+            FilterOperation::Match => "match",
+            FilterOperation::Has => "has",
+            FilterOperation::HasNoneOf => "has_none_of",
+        }
+    }
+}
+
+impl std::fmt::Display for FilterOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// The right-hand side of a filter, either another field or a constant value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterOperand {
+    Field(FieldWithInterval),
+    Value(Value),
+}
+
+impl FilterOperand {
+    /// Get the JSON representation of the operand.
+    pub fn to_json(&self) -> Value {
+        match self {
+            FilterOperand::Field(x) => Value::String(x.to_string()),
+            FilterOperand::Value(x) => x.clone(),
+        }
+    }
+}
+
+impl From<FieldWithInterval> for FilterOperand {
+    fn from(x: FieldWithInterval) -> Self {
+        FilterOperand::Field(x)
+    }
+}
+
+impl From<&FieldWithInterval> for FilterOperand {
+    fn from(x: &FieldWithInterval) -> Self {
+        FilterOperand::Field(x.clone())
+    }
+}
+
+impl From<Value> for FilterOperand {
+    fn from(x: Value) -> Self {
+        FilterOperand::Value(x)
+    }
+}
+
+impl From<f64> for FilterOperand {
+    fn from(x: f64) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+impl From<i64> for FilterOperand {
+    fn from(x: i64) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+impl From<i32> for FilterOperand {
+    fn from(x: i32) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+impl From<bool> for FilterOperand {
+    fn from(x: bool) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+impl From<&str> for FilterOperand {
+    fn from(x: &str) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+impl From<String> for FilterOperand {
+    fn from(x: String) -> Self {
+        FilterOperand::Value(json!(x))
+    }
+}
+
+/// A single condition of the scanner `filter` clause, comparing a field with another field or a constant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub left: FieldWithInterval,
+    pub operation: FilterOperation,
+    pub right: Vec<FilterOperand>,
+}
+
+impl Filter {
+    /// Creates a new `Filter` instance.
+    pub fn new(
+        left: FieldWithInterval,
+        operation: FilterOperation,
+        right: Vec<FilterOperand>,
+    ) -> Self {
+        Self {
+            left,
+            operation,
+            right,
+        }
+    }
+
+    /// `left > right`
+    pub fn greater<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::Greater, vec![right.into()])
+    }
+
+    /// `left < right`
+    pub fn less<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::Less, vec![right.into()])
+    }
+
+    /// `left >= right`
+    pub fn egreater<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::EGreater, vec![right.into()])
+    }
+
+    /// `left <= right`
+    pub fn eless<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::ELess, vec![right.into()])
+    }
+
+    /// `low <= left <= high`
+    pub fn in_range<T1, T2>(left: FieldWithInterval, low: T1, high: T2) -> Self
+    where
+        T1: Into<FilterOperand>,
+        T2: Into<FilterOperand>,
+    {
+        Self::new(
+            left,
+            FilterOperation::InRange,
+            vec![low.into(), high.into()],
+        )
+    }
+
+    /// `left < low || left > high`
+    pub fn not_in_range<T1, T2>(left: FieldWithInterval, low: T1, high: T2) -> Self
+    where
+        T1: Into<FilterOperand>,
+        T2: Into<FilterOperand>,
+    {
+        Self::new(
+            left,
+            FilterOperation::NotInRange,
+            vec![low.into(), high.into()],
+        )
+    }
+
+    /// `left == right`
+    pub fn equal<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::Equal, vec![right.into()])
+    }
+
+    /// `left != right`
+    pub fn nequal<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::NEqual, vec![right.into()])
+    }
+
+    /// `left` crosses `right` in either direction.
+    pub fn crosses<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::Crosses, vec![right.into()])
+    }
+
+    /// `left` crosses above `right`.
+    pub fn crosses_above<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::CrossesAbove, vec![right.into()])
+    }
+
+    /// `left` crosses below `right`.
+    pub fn crosses_below<T: Into<FilterOperand>>(left: FieldWithInterval, right: T) -> Self {
+        Self::new(left, FilterOperation::CrossesBelow, vec![right.into()])
+    }
+
+    /// `left` matches the text `pattern`.
+    pub fn matches<S: AsRef<str>>(left: FieldWithInterval, pattern: S) -> Self {
+        Self::new(left, FilterOperation::Match, vec![pattern.as_ref().into()])
+    }
+
+    /// `left` has any of the given values.
+    pub fn has<S: AsRef<str>>(left: FieldWithInterval, values: &[S]) -> Self {
+        let values = values.iter().map(|x| x.as_ref().into()).collect();
+        Self::new(left, FilterOperation::Has, values)
+    }
+
+    /// `left` has none of the given values.
+    pub fn has_none_of<S: AsRef<str>>(left: FieldWithInterval, values: &[S]) -> Self {
+        let values = values.iter().map(|x| x.as_ref().into()).collect();
+        Self::new(left, FilterOperation::HasNoneOf, values)
+    }
+
+    /// Get the JSON representation of the filter, as expected by the scanner.
+    pub fn to_json(&self) -> Value {
+        let right: Value = match self.operation {
+            FilterOperation::InRange
+            | FilterOperation::NotInRange
+            | FilterOperation::Has
+            | FilterOperation::HasNoneOf => {
+                Value::Array(self.right.iter().map(|x| x.to_json()).collect())
+            }
+            _ => self.right.first().map_or(Value::Null, |x| x.to_json()),
+        };
+        json!({
+            "left": self.left.to_string(),
+            "operation": self.operation.as_ref(),
+            "right": right,
+        })
+    }
+}
+
+/// A boolean expression tree of filters, serialized as the scanner `filter2` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Filter(Filter),
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Creates an expression that matches when all operands match.
+    pub fn and<I, T>(operands: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<FilterExpr>,
+    {
+        FilterExpr::And(operands.into_iter().map(|x| x.into()).collect())
+    }
+
+    /// Creates an expression that matches when any operand matches.
+    pub fn or<I, T>(operands: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<FilterExpr>,
+    {
+        FilterExpr::Or(operands.into_iter().map(|x| x.into()).collect())
+    }
+
+    /// Get the JSON representation of the expression, as expected by the scanner `filter2` clause.
+    /// The root of `filter2` must be an operation, so a single filter is wrapped into an `and`.
+    pub fn to_json(&self) -> Value {
+        match self {
+            FilterExpr::Filter(_) => Self::operation_json("and", std::slice::from_ref(self)),
+            FilterExpr::And(operands) => Self::operation_json("and", operands),
+            FilterExpr::Or(operands) => Self::operation_json("or", operands),
+        }
+    }
+
+    fn operand_json(&self) -> Value {
+        match self {
+            FilterExpr::Filter(x) => json!({ "expression": x.to_json() }),
+            _ => json!({ "operation": self.to_json() }),
+        }
+    }
+
+    fn operation_json(operator: &str, operands: &[FilterExpr]) -> Value {
+        json!({
+            "operator": operator,
+            "operands": operands.iter().map(|x| x.operand_json()).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl From<Filter> for FilterExpr {
+    fn from(x: Filter) -> Self {
+        FilterExpr::Filter(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_to_json() {
+        let rsi = Filter::less(Field::RSI.with_interval(&Interval::Hour1), 30);
+        assert_eq!(
+            rsi.to_json(),
+            json!({"left": "RSI|60", "operation": "less", "right": 30})
+        );
+
+        let close = Filter::greater(
+            Field::Close.with_interval(&Interval::Day1),
+            Field::SMA200.with_interval(&Interval::Day1),
+        );
+        assert_eq!(
+            close.to_json(),
+            json!({"left": "close", "operation": "greater", "right": "SMA200"})
+        );
+
+        let range = Filter::in_range(Field::Change.with_interval(&Interval::Day1), -5.0, 5.0);
+        assert_eq!(
+            range.to_json(),
+            json!({"left": "change", "operation": "in_range", "right": [-5.0, 5.0]})
+        );
+
+        let has = Filter::has(Field::SubType.with_interval(&Interval::Day1), &["spot"]);
+        assert_eq!(
+            has.to_json(),
+            json!({"left": "subtype", "operation": "has", "right": ["spot"]})
+        );
+    }
+
+    #[test]
+    fn test_filter_expr_to_json() {
+        let rsi = Filter::less(Field::RSI.with_interval(&Interval::Hour1), 30);
+        let volume = Filter::greater(
+            Field::RelativeVolume10dCalc.with_interval(&Interval::Day1),
+            2,
+        );
+        let expr = FilterExpr::or([
+            FilterExpr::and([rsi.clone()]),
+            FilterExpr::from(volume.clone()),
+        ]);
+        assert_eq!(
+            expr.to_json(),
+            json!({
+                "operator": "or",
+                "operands": [
+                    {
+                        "operation": {
+                            "operator": "and",
+                            "operands": [{ "expression": rsi.to_json() }],
+                        }
+                    },
+                    { "expression": volume.to_json() },
+                ]
+            })
+        );
+
+        assert_eq!(
+            FilterExpr::from(rsi.clone()).to_json(),
+            json!({
+                "operator": "and",
+                "operands": [{ "expression": rsi.to_json() }],
+            })
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use anyhow::{anyhow, Result};
+use crate::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub enum Interval {
     Undefined(String),
     Min1,
@@ -10,6 +11,7 @@ pub enum Interval {
     Hour1,
     Hour2,
     Hour4,
+    #[default]
     Day1,
     Week1,
     Month1,
@@ -27,13 +29,14 @@ impl Interval {
             "1" | "1m" => Ok(Interval::Min1),
             "5" | "5m" => Ok(Interval::Min5),
             "15" | "15m" => Ok(Interval::Min15),
+            "30" | "30m" => Ok(Interval::Min30),
             "60" | "1h" => Ok(Interval::Hour1),
             "120" | "2h" => Ok(Interval::Hour2),
             "240" | "4h" => Ok(Interval::Hour4),
             "1d" => Ok(Interval::Day1),
-            "1W" => Ok(Interval::Week1),
+            "1W" | "1w" => Ok(Interval::Week1),
             "1M" => Ok(Interval::Month1),
-            _ => Err(anyhow!("Invalid interval: {}", s)),
+            _ => Err(Error::InvalidInterval(s.to_owned())),
         }
     }
 
@@ -66,12 +69,6 @@ impl Interval {
     }
 }
 
-impl Default for Interval {
-    fn default() -> Self {
-        Self::Day1
-    }
-}
-
 impl AsRef<str> for Interval {
     fn as_ref(&self) -> &str {
         match self {
@@ -108,3 +105,48 @@ impl std::fmt::Display for Interval {
         write!(f, "{}", s)
     }
 }
+
+/// Serializes as the representation used in field suffixes, e.g. `60`.
+impl Serialize for Interval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Interval::parse_undefined(&s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        let aliases = [
+            ("1", Interval::Min1),
+            ("1m", Interval::Min1),
+            ("30", Interval::Min30),
+            ("30m", Interval::Min30),
+            ("240", Interval::Hour4),
+            ("4h", Interval::Hour4),
+            ("1W", Interval::Week1),
+            ("1w", Interval::Week1),
+            ("1M", Interval::Month1),
+        ];
+        for (s, interval) in aliases {
+            assert_eq!(Interval::parse(s).ok(), Some(interval), "{}", s);
+        }
+        for interval in Interval::all_intervals() {
+            assert_eq!(&Interval::parse_undefined(interval.as_ref()), interval);
+        }
+        assert!(matches!(
+            Interval::parse("3h"),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert_eq!(Interval::parse_undefined("3h"), Interval::undefined("3h"));
+    }
+}
@@ -1,16 +1,51 @@
+// Lets `#[derive(ScanRow)]`, which refers to `::easytradeview`, be used within the crate.
+extern crate self as easytradeview;
+
 pub mod analysis;
+#[cfg(any(test, feature = "blocking"))]
+pub mod blocking;
+pub mod cache;
+pub mod cassette;
+pub mod error;
 pub mod field;
 pub mod field_attr;
+pub mod filter;
 pub mod interval;
+pub mod metainfo;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod rate_limit;
+pub mod retry;
+pub mod scan;
+pub mod scan_row;
 pub mod screener;
 pub mod symbol;
+pub mod symbol_search;
 pub mod table;
+pub mod ticker;
 pub mod tradingview;
+pub mod transport;
 
 pub use analysis::*;
+pub use cache::*;
+pub use cassette::*;
+pub use error::*;
 pub use field::*;
+pub use field_attr::ValueKind;
+pub use filter::*;
 pub use interval::*;
+pub use metainfo::*;
+pub use rate_limit::*;
+pub use retry::*;
+pub use scan::*;
+pub use scan_row::*;
 pub use screener::*;
 pub use symbol::*;
+pub use symbol_search::*;
 pub use table::*;
+pub use ticker::*;
 pub use tradingview::*;
+pub use transport::*;
+
+#[cfg(feature = "derive")]
+pub use easytradeview_derive::ScanRow;
@@ -0,0 +1,172 @@
+use super::*;
+use crate::field_attr::ValueKind;
+use std::collections::HashMap;
+
+/// The type of the values of a scanner column, as declared by the metainfo endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Number,
+    Price,
+    Percent,
+    Integer,
+    Text,
+    Bool,
+    Time,
+    Set,
+    Map,
+    Other(String),
+}
+
+impl FieldType {
+    /// Parses a type name of the metainfo endpoint, keeping unknown names as `FieldType::Other`.
+    pub fn parse(s: &str) -> FieldType {
+        match s {
+            "number" => FieldType::Number,
+            "price" | "fundamental_price" => FieldType::Price,
+            "percent" => FieldType::Percent,
+            "integer" => FieldType::Integer,
+            "text" => FieldType::Text,
+            "bool" => FieldType::Bool,
+            "time" => FieldType::Time,
+            "set" => FieldType::Set,
+            "map" => FieldType::Map,
+            _ => FieldType::Other(s.to_owned()),
+        }
+    }
+}
+
+impl AsRef<str> for FieldType {
+    fn as_ref(&self) -> &str {
+        match self {
+            FieldType::Number => "number",
+            FieldType::Price => "price",
+            FieldType::Percent => "percent",
+            FieldType::Integer => "integer",
+            FieldType::Text => "text",
+            FieldType::Bool => "bool",
+            FieldType::Time => "time",
+            FieldType::Set => "set",
+            FieldType::Map => "map",
+            FieldType::Other(x) => x,
+        }
+    }
+}
+
+/// A column supported by a screener, as listed by `TradingView::metainfo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInfo {
+    pub name: String,
+    pub field_type: FieldType,
+    pub has_interval: bool,
+    pub enum_values: Option<Vec<String>>,
+}
+
+impl FieldInfo {
+    /// Get the field of the column, an undefined field if the `Field` enum has no variant for it.
+    pub fn field(&self) -> Field {
+        match Field::parse(&self.name) {
+            Ok(field) => field,
+            Err(_) if self.has_interval => Field::undefined(&self.name),
+            Err(_) => Field::undefined_without_interval(&self.name),
+        }
+    }
+
+    /// Get the kind of the values of the column. Text with enum values is an enum; sets, maps and
+    /// unknown types, which have no kind of their own, are text.
+    pub fn value_kind(&self) -> ValueKind {
+        match self.field_type {
+            FieldType::Number => ValueKind::Number,
+            FieldType::Price => ValueKind::Price,
+            FieldType::Percent => ValueKind::Percent,
+            FieldType::Integer => ValueKind::Integer,
+            FieldType::Text if self.enum_values.is_some() => ValueKind::Enum,
+            FieldType::Bool => ValueKind::Bool,
+            FieldType::Time => ValueKind::Timestamp,
+            _ => ValueKind::Text,
+        }
+    }
+
+    /// Parses a metainfo response, `{"fields": [{"n": name, "t": type, "r": values}]}`.
+    /// The endpoint lists a column once per interval, e.g. `RSI` and `RSI|60`, which are merged into one
+    /// entry taking an interval. Entries keep the order of the first occurrence of their column.
+    pub fn from_metainfo(json: &Value) -> Vec<FieldInfo> {
+        let mut fields: Vec<FieldInfo> = Vec::new();
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        let data_array: Vec<Value> = vec![];
+        for data in json["fields"].as_array().unwrap_or(&data_array) {
+            let Some(column) = data["n"].as_str() else {
+                continue;
+            };
+            let (name, interval) = match column.split_once('|') {
+                Some((name, _)) => (name, true),
+                None => (column, false),
+            };
+            if let Some(&index) = indexes.get(name) {
+                fields[index].has_interval |= interval;
+                continue;
+            }
+            indexes.insert(name.to_owned(), fields.len());
+            fields.push(FieldInfo {
+                name: name.to_owned(),
+                field_type: FieldType::parse(data["t"].as_str().unwrap_or_default()),
+                has_interval: interval,
+                enum_values: Self::enum_values(&data["r"]),
+            });
+        }
+        fields
+    }
+
+    /// Get the allowed values of a column, given either as ids or as `{"id", "name"}` objects.
+    /// Numeric bounds, e.g. `[-1, 1]` of the recommendations, are not enum values.
+    fn enum_values(json: &Value) -> Option<Vec<String>> {
+        let values: Vec<String> = json
+            .as_array()?
+            .iter()
+            .filter_map(|x| x.as_str().or_else(|| x["id"].as_str()))
+            .map(|x| x.to_owned())
+            .collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_metainfo() {
+        let json = json!({
+            "fields": [
+                {"n": "Recommend.All", "t": "number", "r": [-1, 1]},
+                {"n": "type", "t": "text", "r": [{"id": "spot", "name": "Spot"}, "swap"]},
+                {"n": "RSI|60", "t": "number", "r": null},
+                {"n": "RSI", "t": "number", "r": null},
+                {"n": "float_shares_outstanding", "t": "fundamental_price"},
+            ]
+        });
+        let fields = FieldInfo::from_metainfo(&json);
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0].field(), Field::RecommendAll);
+        assert_eq!(fields[0].enum_values, None);
+        assert_eq!(fields[1].value_kind(), ValueKind::Enum);
+        assert_eq!(
+            fields[1].enum_values,
+            Some(vec!["spot".to_owned(), "swap".to_owned()])
+        );
+        assert!(!fields[1].has_interval);
+        assert_eq!(
+            (fields[2].field(), fields[2].has_interval),
+            (Field::RSI, true)
+        );
+        assert_eq!(fields[3].field_type, FieldType::Price);
+        assert_eq!(
+            fields[3].field(),
+            Field::undefined_without_interval("float_shares_outstanding")
+        );
+    }
+}
@@ -0,0 +1,611 @@
+use super::*;
+use crate::field_attr::FieldAttr;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const FIXTURE: &str = include_str!("../fixtures/scanner.json");
+// Smaller than the 50 results of TradingView, so that paging shows on the small fixture.
+const SYMBOL_SEARCH_PAGE_SIZE: usize = 10;
+
+/// A symbol of the mock dataset, with its values keyed by column, e.g. `close|60`.
+#[derive(Debug, Clone)]
+pub struct MockSymbol {
+    pub screener: String,
+    pub ticker: Ticker,
+    pub values: HashMap<String, Value>,
+}
+
+impl MockSymbol {
+    /// Get the value of a column, or `Value::Null` if the dataset has none.
+    /// Columns of fields without interval fall back to the bare field name.
+    pub fn value(&self, column: &str) -> Value {
+        if let Some(value) = self.values.get(column) {
+            return value.clone();
+        }
+        match column.split_once('|') {
+            Some((name, _)) if FieldAttr::find_by_name(name).is_some_and(|x| !x.has_interval) => {
+                self.values.get(name).cloned().unwrap_or(Value::Null)
+            }
+            _ => Value::Null,
+        }
+    }
+}
+
+/// The symbols served by a `MockScanner`.
+#[derive(Debug, Clone, Default)]
+pub struct MockDataset {
+    pub symbols: Vec<MockSymbol>,
+}
+
+impl MockDataset {
+    /// Get the dataset bundled with the crate, a snapshot of crypto and US stock symbols.
+    pub fn fixture() -> MockDataset {
+        Self::from_json(&serde_json::from_str(FIXTURE).expect("fixture is valid json"))
+            .expect("fixture is a valid dataset")
+    }
+
+    /// Parses a dataset in the form of `{"symbols": [{"screener", "symbol", "values": {...}}]}`.
+    pub fn from_json(json: &Value) -> Result<MockDataset> {
+        let mut symbols = Vec::new();
+        let data_array: Vec<Value> = vec![];
+        for data in json["symbols"].as_array().unwrap_or(&data_array) {
+            let ticker = Ticker::parse(data["symbol"].as_str().unwrap_or_default())?;
+            let values = data["values"]
+                .as_object()
+                .map(|x| x.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+                .unwrap_or_default();
+            symbols.push(MockSymbol {
+                screener: data["screener"].as_str().unwrap_or_default().to_owned(),
+                ticker,
+                values,
+            });
+        }
+        Ok(MockDataset { symbols })
+    }
+
+    /// Finds a symbol by ticker.
+    pub fn find(&self, ticker: &Ticker) -> Option<&MockSymbol> {
+        self.symbols.iter().find(|x| &x.ticker == ticker)
+    }
+}
+
+#[derive(Debug)]
+struct MockState {
+    dataset: MockDataset,
+    requests: AtomicUsize,
+}
+
+/// An offline stand-in for the TradingView scanner, serving `/symbol`, `/{screener}/scan`,
+/// `/{screener}/metainfo` and `/symbol_search/v3/` over a `MockDataset` on a local port. The server stops when the instance is dropped.
+#[derive(Debug)]
+pub struct MockScanner {
+    addr: SocketAddr,
+    state: Arc<MockState>,
+    handle: JoinHandle<()>,
+}
+
+impl MockScanner {
+    /// Starts a mock scanner serving the bundled fixture dataset.
+    pub async fn start() -> std::io::Result<MockScanner> {
+        Self::start_with_dataset(MockDataset::fixture()).await
+    }
+
+    /// Starts a mock scanner serving the given dataset.
+    pub async fn start_with_dataset(dataset: MockDataset) -> std::io::Result<MockScanner> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(MockState {
+            dataset,
+            requests: AtomicUsize::new(0),
+        });
+        let handle = {
+            let state = state.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve(stream, state.clone()));
+                }
+            })
+        };
+        Ok(MockScanner {
+            addr,
+            state,
+            handle,
+        })
+    }
+
+    /// Get the base URL of the mock scanner, to be passed to `TradingViewBuilder::scanner_url`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Creates a new TradingView instance sending its requests to the mock scanner.
+    pub fn tradingview<S1, S2>(&self, screener: S1, exchange: S2) -> TradingView
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        TradingView::builder()
+            .screener(screener)
+            .exchange(exchange)
+            .scanner_url(self.url())
+            .build()
+            .expect("mock url is valid")
+    }
+
+    /// Creates a new SymbolSearch instance sending its requests to the mock scanner.
+    pub fn symbol_search(&self) -> SymbolSearch {
+        SymbolSearch::with_base_url(self.url()).expect("mock url is valid")
+    }
+
+    /// Get the dataset served by the mock scanner.
+    pub fn dataset(&self) -> &MockDataset {
+        &self.state.dataset
+    }
+
+    /// Get the number of requests received so far.
+    pub fn request_count(&self) -> usize {
+        self.state.requests.load(atomic::Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockScanner {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Reads one HTTP/1.1 request from the connection, answers it and closes the connection.
+async fn serve(stream: TcpStream, state: Arc<MockState>) {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).await.is_err() {
+        return;
+    }
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) if line.trim().is_empty() => break,
+            Ok(_) => {
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).await.is_err() {
+        return;
+    }
+
+    state.requests.fetch_add(1, atomic::Ordering::SeqCst);
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (status, json) = handle(&state.dataset, method, target, &body);
+
+    let body = json.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    );
+    let mut stream = reader.into_inner();
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "",
+    }
+}
+
+fn error_json(status: u16, message: String) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+/// Routes a request, returning the status and the JSON body of the response.
+fn handle(dataset: &MockDataset, method: &str, target: &str, body: &[u8]) -> (u16, Value) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params: HashMap<String, String> = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    let segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["symbol"]) => handle_symbol(dataset, &params),
+        ("GET", ["symbol_search", "v3"]) => handle_symbol_search(dataset, &params),
+        ("POST", [screener, "metainfo"]) => handle_metainfo(dataset, screener),
+        ("POST", [screener, "scan"]) => match serde_json::from_slice(body) {
+            Ok(body) => handle_scan(dataset, screener, &body),
+            Err(err) => error_json(400, format!("Invalid json: {}", err)),
+        },
+        (_, ["symbol"]) | (_, [_, "scan"]) | (_, [_, "metainfo"]) => {
+            error_json(405, "Method not allowed".to_owned())
+        }
+        _ => error_json(404, format!("Not found: {}", path)),
+    }
+}
+
+/// Checks that every column names a known field.
+fn check_columns(columns: &[String]) -> Result<(), (u16, Value)> {
+    for column in columns {
+        let name = column.split('|').next().unwrap_or_default();
+        if Field::parse(name).is_err() {
+            return Err(error_json(400, format!("Unknown field \"{}\"", column)));
+        }
+    }
+    Ok(())
+}
+
+fn handle_symbol(dataset: &MockDataset, params: &HashMap<String, String>) -> (u16, Value) {
+    let columns: Vec<String> = params
+        .get("fields")
+        .map(|x| x.split(',').map(|x| x.to_owned()).collect())
+        .unwrap_or_default();
+    if let Err(err) = check_columns(&columns) {
+        return err;
+    }
+    let ticker = Ticker::parse_symbol(params.get("symbol").map_or("", |x| x.as_str()));
+    match dataset.find(&ticker) {
+        Some(symbol) => {
+            let values: serde_json::Map<String, Value> = columns
+                .iter()
+                .map(|x| (x.clone(), symbol.value(x)))
+                .collect();
+            (200, Value::Object(values))
+        }
+        None if params.get("no_404").is_some_and(|x| x == "true") => (200, json!({})),
+        None => error_json(404, format!("Symbol not found: {}", ticker)),
+    }
+}
+
+fn handle_symbol_search(dataset: &MockDataset, params: &HashMap<String, String>) -> (u16, Value) {
+    let param = |key: &str| params.get(key).map_or("", |x| x.as_str());
+    let text = param("text").to_lowercase();
+    let exchange = param("exchange");
+    let search_type = param("search_type");
+    let start: usize = param("start").parse().unwrap_or(0);
+
+    let symbols: Vec<Value> = dataset
+        .symbols
+        .iter()
+        .filter(|x| {
+            x.ticker.symbol.to_lowercase().contains(&text)
+                || x.value("description")
+                    .as_str()
+                    .is_some_and(|x| x.to_lowercase().contains(&text))
+        })
+        .filter(|x| exchange.is_empty() || x.ticker.exchange == exchange)
+        .filter(|x| {
+            search_type.is_empty() || x.screener == search_type || x.value("type") == search_type
+        })
+        .map(|x| {
+            json!({
+                "symbol": x.ticker.symbol,
+                "description": x.value("description"),
+                "type": x.value("type"),
+                "exchange": x.ticker.exchange,
+                "currency_code": x.value("currency"),
+                "country": if x.screener == "america" { json!("US") } else { Value::Null },
+            })
+        })
+        .collect();
+    let page: Vec<Value> = symbols
+        .iter()
+        .skip(start)
+        .take(SYMBOL_SEARCH_PAGE_SIZE)
+        .cloned()
+        .collect();
+    let remaining = symbols.len().saturating_sub(start + page.len());
+    (
+        200,
+        json!({ "symbols_remaining": remaining, "symbols": page }),
+    )
+}
+
+/// Lists every field of `FieldAttr`, with the values of the screener in the dataset as the values of enums.
+fn handle_metainfo(dataset: &MockDataset, screener: &str) -> (u16, Value) {
+    let symbols: Vec<&MockSymbol> = dataset
+        .symbols
+        .iter()
+        .filter(|x| x.screener == screener)
+        .collect();
+    if symbols.is_empty() {
+        return error_json(404, format!("Unknown screener \"{}\"", screener));
+    }
+
+    let mut fields = Vec::new();
+    for attr in FieldAttr::all_field_attrs() {
+        let field_type = match attr.kind {
+            ValueKind::Number => "number",
+            ValueKind::Integer => "integer",
+            ValueKind::Percent => "percent",
+            ValueKind::Price => "price",
+            ValueKind::Text | ValueKind::Enum => "text",
+            ValueKind::Timestamp => "time",
+            ValueKind::Bool => "bool",
+        };
+        let enum_values = if attr.kind == ValueKind::Enum {
+            let mut values: Vec<String> = symbols
+                .iter()
+                .filter_map(|x| x.value(attr.name).as_str().map(|x| x.to_owned()))
+                .collect();
+            values.sort_unstable();
+            values.dedup();
+            json!(values)
+        } else {
+            Value::Null
+        };
+        fields.push(json!({ "n": attr.name, "t": field_type, "r": enum_values }));
+        if attr.has_interval {
+            for interval in Interval::all_intervals() {
+                let suffix = interval.as_field_suffix();
+                if !suffix.is_empty() {
+                    fields.push(
+                        json!({ "n": attr.name.to_owned() + &suffix, "t": field_type, "r": null }),
+                    );
+                }
+            }
+        }
+    }
+    (200, json!({ "fields": fields }))
+}
+
+fn handle_scan(dataset: &MockDataset, screener: &str, body: &Value) -> (u16, Value) {
+    if !dataset.symbols.iter().any(|x| x.screener == screener) {
+        return error_json(404, format!("Unknown screener \"{}\"", screener));
+    }
+    let columns: Vec<String> = string_array(&body["columns"]);
+    if let Err(err) = check_columns(&columns) {
+        return err;
+    }
+
+    let tickers = string_array(&body["symbols"]["tickers"]);
+    let mut symbols: Vec<&MockSymbol> = if tickers.is_empty() {
+        let exchanges = string_array(&body["symbols"]["query"]["exchanges"]);
+        let types = string_array(&body["symbols"]["query"]["types"]);
+        dataset
+            .symbols
+            .iter()
+            .filter(|x| x.screener == screener)
+            .filter(|x| exchanges.is_empty() || exchanges.contains(&x.ticker.exchange))
+            .filter(|x| types.is_empty() || types.iter().any(|t| x.value("type") == *t))
+            .collect()
+    } else {
+        tickers
+            .iter()
+            .filter_map(|x| dataset.find(&Ticker::parse_symbol(x)))
+            .filter(|x| x.screener == screener)
+            .collect()
+    };
+
+    let mut filtered = Vec::new();
+    for symbol in symbols.drain(..) {
+        let mut matched = true;
+        for filter in body["filter"].as_array().into_iter().flatten() {
+            match eval_filter(symbol, filter) {
+                Ok(x) => matched &= x,
+                Err(err) => return error_json(400, err),
+            }
+        }
+        if !body["filter2"].is_null() {
+            match eval_expr(symbol, &body["filter2"]) {
+                Ok(x) => matched &= x,
+                Err(err) => return error_json(400, err),
+            }
+        }
+        if matched {
+            filtered.push(symbol);
+        }
+    }
+
+    if let Some(sort_by) = body["sort"]["sortBy"].as_str() {
+        let desc = body["sort"]["sortOrder"].as_str() == Some("desc");
+        filtered.sort_by(|a, b| {
+            let (a, b) = (a.value(sort_by), b.value(sort_by));
+            // Nulls always come last, whatever the order.
+            match (a.is_null(), b.is_null()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                _ if desc => compare(&b, &a).unwrap_or(Ordering::Equal),
+                _ => compare(&a, &b).unwrap_or(Ordering::Equal),
+            }
+        });
+    }
+
+    let total_count = filtered.len();
+    let (from, to) = match body["range"].as_array().map(|x| x.as_slice()) {
+        Some([from, to]) => (
+            from.as_u64().unwrap_or(0) as usize,
+            to.as_u64().unwrap_or(0) as usize,
+        ),
+        _ => (0, total_count),
+    };
+    let data: Vec<Value> = filtered
+        .iter()
+        .skip(from)
+        .take(to.saturating_sub(from))
+        .map(|x| {
+            let d: Vec<Value> = columns.iter().map(|c| x.value(c)).collect();
+            json!({ "s": x.ticker.to_string(), "d": d })
+        })
+        .collect();
+    (200, json!({ "totalCount": total_count, "data": data }))
+}
+
+fn string_array(json: &Value) -> Vec<String> {
+    json.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|x| x.as_str().map(|x| x.to_owned()))
+        .collect()
+}
+
+/// Compares two numbers or two strings.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Resolves the right operand of a filter: a string naming a field is replaced by the value of that column.
+fn operand(symbol: &MockSymbol, right: &Value) -> Value {
+    match right.as_str() {
+        Some(s) if Field::parse(s.split('|').next().unwrap_or_default()).is_ok() => symbol.value(s),
+        _ => right.clone(),
+    }
+}
+
+fn eval_filter(symbol: &MockSymbol, filter: &Value) -> std::result::Result<bool, String> {
+    let left = symbol.value(filter["left"].as_str().unwrap_or_default());
+    let operation = filter["operation"].as_str().unwrap_or_default();
+    let right = &filter["right"];
+    if left.is_null() {
+        return Ok(false);
+    }
+    let cmp = || compare(&left, &operand(symbol, right));
+    let range = || -> Option<(Value, Value)> {
+        match right.as_array().map(|x| x.as_slice()) {
+            Some([low, high]) => Some((operand(symbol, low), operand(symbol, high))),
+            _ => None,
+        }
+    };
+    let in_range = || {
+        range().is_some_and(|(low, high)| {
+            compare(&left, &low).is_some_and(|x| x.is_ge())
+                && compare(&left, &high).is_some_and(|x| x.is_le())
+        })
+    };
+    let has = |x: &Value| match &left {
+        Value::Array(items) => items.contains(x),
+        _ => &left == x,
+    };
+    let matched = match operation {
+        "greater" => cmp().is_some_and(|x| x.is_gt()),
+        "less" => cmp().is_some_and(|x| x.is_lt()),
+        "egreater" => cmp().is_some_and(|x| x.is_ge()),
+        "eless" => cmp().is_some_and(|x| x.is_le()),
+        "equal" => cmp().map_or(left == operand(symbol, right), |x| x.is_eq()),
+        "nequal" => cmp().map_or(left != operand(symbol, right), |x| x.is_ne()),
+        "in_range" => in_range(),
+        "not_in_range" => !in_range(),
+        "match" => match (left.as_str(), right.as_str()) {
+            (Some(left), Some(right)) => left.to_lowercase().contains(&right.to_lowercase()),
+            _ => false,
+        },
+        "has" => right.as_array().into_iter().flatten().any(has),
+        "has_none_of" => !right.as_array().into_iter().flatten().any(has),
+        // The dataset is a single snapshot without previous values, so nothing ever crosses.
+        "crosses" | "crosses_above" | "crosses_below" => false,
+        _ => return Err(format!("Unknown operation \"{}\"", operation)),
+    };
+    Ok(matched)
+}
+
+fn eval_expr(symbol: &MockSymbol, expr: &Value) -> std::result::Result<bool, String> {
+    let mut results = Vec::new();
+    for operand in expr["operands"].as_array().into_iter().flatten() {
+        let result = if let Some(filter) = operand.get("expression") {
+            eval_filter(symbol, filter)?
+        } else if let Some(expr) = operand.get("operation") {
+            eval_expr(symbol, expr)?
+        } else {
+            return Err("Invalid filter2 operand".to_owned());
+        };
+        results.push(result);
+    }
+    match expr["operator"].as_str() {
+        Some("and") => Ok(results.iter().all(|x| *x)),
+        Some("or") => Ok(results.iter().any(|x| *x)),
+        other => Err(format!("Unknown operator {:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_mock_scan() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
+        let close = Field::Close.with_interval(&Interval::Day1);
+        let query = ScanQuery::new(&[close.clone(), Field::Name.with_interval(&Interval::Day1)])
+            .exchanges(&["BINANCE"])
+            .filter(Filter::greater(close.clone(), 1.0))
+            .sort_by(close.clone(), Order::Desc)
+            .range(1, 3);
+        let page = tradingview.scan(&query).await?;
+
+        let mut expected: Vec<&MockSymbol> = mock
+            .dataset()
+            .symbols
+            .iter()
+            .filter(|x| x.ticker.exchange == "BINANCE")
+            .filter(|x| x.value("close").as_f64().unwrap_or_default() > 1.0)
+            .collect();
+        expected.sort_by(|a, b| compare(&b.value("close"), &a.value("close")).unwrap());
+        assert_eq!(page.total_count, Some(expected.len()));
+        assert_eq!(page.symbols.len(), 3);
+        for (values, symbol) in page.symbols.iter().zip(expected.iter().skip(1)) {
+            assert_eq!(values.symbol(), &symbol.ticker);
+            assert_eq!(values.values()[&close], symbol.value("close"));
+        }
+        assert_eq!(mock.request_count(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_filter_expr() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
+        let name = Field::Name.with_interval(&Interval::Day1);
+        let expr = FilterExpr::or([
+            Filter::equal(name.clone(), "ETHBTC"),
+            Filter::has(Field::Exchange.with_interval(&Interval::Day1), &["KRAKEN"]),
+        ]);
+        let query = ScanQuery::new(&[Field::Exchange.with_interval(&Interval::Day1)])
+            .filter(Filter::matches(name, "btc"))
+            .filter_expr(expr);
+        let page = tradingview.scan(&query).await?;
+        let mut tickers: Vec<String> = page
+            .symbols
+            .iter()
+            .map(|x| x.symbol().to_string())
+            .collect();
+        tickers.sort();
+        assert_eq!(tickers, ["BINANCE:ETHBTC", "KRAKEN:BTCUSD"]);
+
+        let query =
+            ScanQuery::new(
+                &[Field::Undefined("unknown".to_owned()).with_interval(&Interval::Day1)],
+            );
+        assert!(matches!(
+            tradingview.scan(&query).await,
+            Err(Error::Http { status: 400, .. })
+        ));
+        Ok(())
+    }
+}
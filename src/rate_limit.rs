@@ -0,0 +1,168 @@
+use crate::{Error, Result};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
+
+/// A client-side rate limiter, combining a token bucket for requests per second
+/// with a bound on the number of concurrent requests.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    bucket: Option<Mutex<TokenBucket>>,
+    semaphore: Option<Semaphore>,
+    queue_depth: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Takes a token, or returns how long to wait until one is available.
+    fn take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+/// Permission to send one request, held until the request completes.
+#[derive(Debug)]
+pub struct RateLimitPermit<'a> {
+    _permit: Option<SemaphorePermit<'a>>,
+}
+
+/// Decrements the queue depth when dropped, including when the waiting future is cancelled.
+struct QueueGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of requests per second, allowing bursts of up to one second of requests.
+    /// Returns `Error::InvalidRateLimit` unless the rate is a positive finite number.
+    pub fn requests_per_second(mut self, requests_per_second: f64) -> Result<Self> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(Error::InvalidRateLimit(format!(
+                "{} requests per second",
+                requests_per_second
+            )));
+        }
+        let capacity = requests_per_second.max(1.0);
+        self.bucket = Some(Mutex::new(TokenBucket {
+            rate: requests_per_second,
+            capacity,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }));
+        Ok(self)
+    }
+
+    /// Limits the number of requests in flight at the same time.
+    /// Returns `Error::InvalidRateLimit` if the limit is 0, which would block every request.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Result<Self> {
+        if max_concurrent_requests == 0 {
+            return Err(Error::InvalidRateLimit("0 concurrent requests".to_owned()));
+        }
+        self.semaphore = Some(Semaphore::new(max_concurrent_requests));
+        Ok(self)
+    }
+
+    /// Get the number of requests waiting for a permit.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Waits until a request is allowed to be sent.
+    pub async fn acquire(&self) -> RateLimitPermit<'_> {
+        self.queue_depth.fetch_add(1, Ordering::SeqCst);
+        let _guard = QueueGuard(&self.queue_depth);
+
+        let permit = match &self.semaphore {
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+        if let Some(bucket) = &self.bucket {
+            loop {
+                let wait = bucket.lock().await.take();
+                match wait {
+                    Ok(()) => break,
+                    Err(wait) => tokio::time::sleep(wait).await,
+                }
+            }
+        }
+        RateLimitPermit { _permit: permit }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_requests_per_second() -> Result<()> {
+        let limiter = RateLimiter::new().requests_per_second(50.0)?;
+        let start = Instant::now();
+        for _ in 0..60 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_limits() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                RateLimiter::new().requests_per_second(rate),
+                Err(Error::InvalidRateLimit(_))
+            ));
+        }
+        assert!(matches!(
+            RateLimiter::new().max_concurrent_requests(0),
+            Err(Error::InvalidRateLimit(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_requests() -> Result<()> {
+        let limiter = Arc::new(RateLimiter::new().max_concurrent_requests(1)?);
+        let permit = limiter.acquire().await;
+        assert_eq!(limiter.queue_depth(), 0);
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move {
+                limiter.acquire().await;
+            })
+        };
+        while limiter.queue_depth() == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(limiter.queue_depth(), 1);
+
+        drop(permit);
+        waiter.await.unwrap();
+        assert_eq!(limiter.queue_depth(), 0);
+        Ok(())
+    }
+}
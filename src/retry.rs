@@ -0,0 +1,264 @@
+use crate::{Error, Result};
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Controls how failed requests are retried.
+/// Connection errors, timeouts, `429 Too Many Requests` and `5xx` responses are retried,
+/// other client errors such as `400 Bad Request` are not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the exponential backoff.
+    pub max_backoff: Duration,
+    /// Factor applied to the backoff after each retry, a non-negative finite number.
+    pub multiplier: f64,
+    /// Relative amount of randomness added to the backoff, between 0 and 1.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the initial and maximum backoff.
+    pub fn backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor applied to the backoff after each retry.
+    /// Returns `Error::InvalidRetryPolicy` unless the factor is a non-negative finite number.
+    pub fn multiplier(mut self, multiplier: f64) -> Result<Self> {
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(Error::InvalidRetryPolicy(format!(
+                "{} backoff multiplier",
+                multiplier
+            )));
+        }
+        self.multiplier = multiplier;
+        Ok(self)
+    }
+
+    /// Sets the relative amount of randomness added to the backoff.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Checks if another attempt is allowed after `attempt` attempts.
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// Get the delay before the next attempt, after `attempt` failed attempts.
+    /// Out of range fields give no delay when negative, and `max_backoff` when too large to be a duration.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let backoff = backoff.min(self.max_backoff.as_secs_f64());
+        let factor = if self.jitter > 0.0 {
+            let jitter = self.jitter.min(1.0);
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        } else {
+            1.0
+        };
+        // `max` also turns NaN into 0.
+        Duration::try_from_secs_f64((backoff * factor).max(0.0)).unwrap_or(self.max_backoff)
+    }
+
+    /// Checks if a response with the given status is worth retrying.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// Get the delay requested by the `Retry-After` header, given either in seconds or as an HTTP date.
+    /// A date in the past is no delay.
+    pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+        let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        let date = parse_http_date(value)?;
+        Some(
+            date.duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+
+    /// Get the delay before retrying a response, honouring its `Retry-After` header up to `max_backoff`.
+    pub fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after.map_or_else(|| self.delay(attempt), |x| x.min(self.max_backoff))
+    }
+}
+
+/// Parses an HTTP date in the preferred format of RFC 9110, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn parse_http_date(s: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let (_, date) = s.split_once(", ")?;
+    let parts: Vec<&str> = date.split(' ').collect();
+    let [day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+    let day: u64 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|x| x == month)? as u64 + 1;
+    let year: u64 = year.parse().ok()?;
+    let time: Vec<u64> = time
+        .split(':')
+        .map(|x| x.parse().ok())
+        .collect::<Option<_>>()?;
+    let [hour, minute, second] = time.as_slice() else {
+        return None;
+    };
+    if year < 1970 || !(1..=31).contains(&day) || *hour > 23 || *minute > 59 || *second > 60 {
+        return None;
+    }
+
+    // Days since the epoch of a date of the proleptic Gregorian calendar, counting years from March.
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let days = 365 * y + y / 4 - y / 100 + y / 400 + (153 * m + 2) / 5 + day - 1 - 719_468;
+    let secs = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default()
+            .backoff(Duration::from_secs(1), Duration::from_secs(5))
+            .jitter(0.0);
+        assert_eq!(policy.delay(1), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(3), Duration::from_secs(4));
+        assert_eq!(policy.delay(4), Duration::from_secs(5));
+
+        let policy = policy.jitter(0.5);
+        for _ in 0..100 {
+            let delay = policy.delay(1);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_millis(1500));
+        }
+    }
+
+    #[test]
+    fn test_invalid_retry_policy() {
+        for multiplier in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                RetryPolicy::default().multiplier(multiplier),
+                Err(Error::InvalidRetryPolicy(_))
+            ));
+        }
+        assert!(RetryPolicy::default().multiplier(0.0).is_ok());
+
+        // Out of range public fields must not panic.
+        let policy = RetryPolicy {
+            multiplier: -1.0,
+            jitter: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay(2), Duration::ZERO);
+        assert!(policy.delay(1) <= Duration::from_secs(1));
+        let policy = RetryPolicy {
+            multiplier: f64::NAN,
+            jitter: f64::NAN,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay(2), policy.max_backoff);
+        let policy = RetryPolicy {
+            initial_backoff: Duration::MAX,
+            max_backoff: Duration::MAX,
+            jitter: 1.0,
+            ..Default::default()
+        };
+        for attempt in 1..10 {
+            assert!(policy.delay(attempt) <= Duration::MAX);
+        }
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(RetryPolicy::is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!RetryPolicy::none().should_retry(1));
+        assert!(RetryPolicy::default().should_retry(2));
+        assert!(!RetryPolicy::default().should_retry(3));
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(RetryPolicy::retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(
+            RetryPolicy::retry_after(&headers),
+            Some(Duration::from_secs(7))
+        );
+        headers.insert(
+            RETRY_AFTER,
+            "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap(),
+        );
+        assert_eq!(RetryPolicy::retry_after(&headers), Some(Duration::ZERO));
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(RetryPolicy::retry_after(&headers), None);
+
+        let policy = RetryPolicy::default().backoff(Duration::from_secs(1), Duration::from_secs(5));
+        assert_eq!(
+            policy.retry_delay(1, Some(Duration::from_secs(3600))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.retry_delay(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_http_date() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(UNIX_EPOCH + Duration::from_secs(784_111_777))
+        );
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_164_800))
+        );
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 UTC"), None);
+    }
+}
@@ -0,0 +1,204 @@
+use super::*;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl AsRef<str> for Order {
+    fn as_ref(&self) -> &str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+/// A scanner request: which symbols to scan, which columns to retrieve, and how to filter, sort and page the result.
+#[derive(Debug, Clone, Default)]
+pub struct ScanQuery {
+    pub tickers: Vec<Ticker>,
+    pub exchanges: Vec<String>,
+    pub types: Vec<String>,
+    pub columns: Vec<FieldWithInterval>,
+    pub filters: Vec<Filter>,
+    pub filter_expr: Option<FilterExpr>,
+    pub sort: Option<(FieldWithInterval, Order)>,
+    pub range: Option<(usize, usize)>,
+}
+
+impl ScanQuery {
+    /// Creates a new `ScanQuery` instance retrieving the given columns.
+    pub fn new(columns: &[FieldWithInterval]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Restricts the scan to the given tickers.
+    pub fn tickers<T: ToTicker>(mut self, tickers: &[T]) -> Self {
+        self.tickers = tickers.iter().map(|x| x.to_ticker()).collect();
+        self
+    }
+
+    /// Restricts the scan to the given exchanges.
+    pub fn exchanges<S: AsRef<str>>(mut self, exchanges: &[S]) -> Self {
+        self.exchanges = exchanges.iter().map(|x| x.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Restricts the scan to the given symbol types.
+    pub fn types<S: AsRef<str>>(mut self, types: &[S]) -> Self {
+        self.types = types.iter().map(|x| x.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Adds a filter that every returned symbol must match.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Adds filters that every returned symbol must match.
+    pub fn filters(mut self, filters: &[Filter]) -> Self {
+        self.filters.extend(filters.iter().cloned());
+        self
+    }
+
+    /// Sets the filter expression that every returned symbol must match.
+    pub fn filter_expr(mut self, filter_expr: FilterExpr) -> Self {
+        self.filter_expr = Some(filter_expr);
+        self
+    }
+
+    /// Sorts the result by the given field on the server side.
+    pub fn sort_by(mut self, field: FieldWithInterval, order: Order) -> Self {
+        self.sort = Some((field, order));
+        self
+    }
+
+    /// Returns at most `limit` symbols, skipping the first `offset` ones.
+    pub fn range(mut self, offset: usize, limit: usize) -> Self {
+        self.range = Some((offset, limit));
+        self
+    }
+
+    /// Get the JSON representation of the query, as expected by the scanner.
+    pub fn to_json(&self) -> Value {
+        let tickers: Vec<String> = self.tickers.iter().map(|x| x.to_string()).collect();
+        let columns: Vec<String> = self.columns.iter().map(|x| x.to_string()).collect();
+        let filters: Vec<Value> = self.filters.iter().map(|x| x.to_json()).collect();
+
+        let mut data = json!({
+            "symbols": {
+                "tickers": tickers,
+                "query": {
+                    "types": self.types,
+                    "exchanges": self.exchanges,
+                }
+            },
+            "filter": filters,
+            "columns": columns
+        });
+        if let Some(filter_expr) = &self.filter_expr {
+            data["filter2"] = filter_expr.to_json();
+        }
+        if let Some((field, order)) = &self.sort {
+            data["sort"] = json!({
+                "sortBy": field.to_string(),
+                "sortOrder": order.as_ref(),
+            });
+        }
+        if let Some((offset, limit)) = self.range {
+            data["range"] = json!([offset, offset.saturating_add(limit)]);
+        }
+        data
+    }
+}
+
+/// A page of scan results, along with the total number of symbols matching the query.
+#[derive(Debug, Default)]
+pub struct ScanPage {
+    /// The total number of symbols matching the query, if the scanner reported it.
+    pub total_count: Option<usize>,
+    pub symbols: Vec<TimedSymbolValues>,
+}
+
+impl ScanPage {
+    /// Parses a scanner response, mapping each row to the columns of the query.
+    pub fn from_json(json: &Value, columns: &[FieldWithInterval]) -> ScanPage {
+        let mut symbols: Vec<TimedSymbolValues> = Vec::new();
+        let data_array: Vec<Value> = vec![];
+        let data_array = json["data"].as_array().unwrap_or(&data_array);
+        for data in data_array.iter() {
+            if let Some(symbol) = data["s"].as_str() {
+                let mut symbol_values = TimedSymbolValues::new(symbol);
+                if let Some(arr) = data["d"].as_array() {
+                    for (field, v) in columns.iter().zip(arr.iter()) {
+                        symbol_values
+                            .values_mut()
+                            .insert(field.clone(), v.to_owned());
+                    }
+                }
+                symbols.push(symbol_values);
+            }
+        }
+        let total_count = json["totalCount"].as_u64().map(|x| x as usize);
+        ScanPage {
+            total_count,
+            symbols,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_query_to_json() {
+        let volume = Field::RelativeVolume10dCalc.with_interval(&Interval::Day1);
+        let query = ScanQuery::new(&[Field::Close.with_interval(&Interval::Hour1)])
+            .types(&["spot"])
+            .sort_by(volume, Order::Desc)
+            .range(50, 50);
+        let json = query.to_json();
+        assert_eq!(json["columns"], json!(["close|60"]));
+        assert_eq!(json["symbols"]["query"]["types"], json!(["spot"]));
+        assert_eq!(
+            json["sort"],
+            json!({"sortBy": "relative_volume_10d_calc", "sortOrder": "desc"})
+        );
+        assert_eq!(json["range"], json!([50, 100]));
+        assert!(json.get("filter2").is_none());
+
+        let query = ScanQuery::new(&[]).range(10, usize::MAX);
+        assert_eq!(query.to_json()["range"], json!([10, usize::MAX]));
+    }
+
+    #[test]
+    fn test_scan_page_from_json() {
+        let columns = [
+            Field::Close.with_interval(&Interval::Day1),
+            Field::Volume.with_interval(&Interval::Day1),
+        ];
+        let json = json!({
+            "totalCount": 1234,
+            "data": [
+                {"s": "OKX:BTCUSDT", "d": [60000.0, 10.5]},
+                {"s": "OKX:ETHUSDT", "d": [3000.0, null]},
+            ]
+        });
+        let page = ScanPage::from_json(&json, &columns);
+        assert_eq!(page.total_count, Some(1234));
+        assert_eq!(page.symbols.len(), 2);
+        assert_eq!(page.symbols[0].symbol(), &Ticker::new("OKX", "BTCUSDT"));
+        assert_eq!(page.symbols[1].values()[&columns[1]], Value::Null);
+
+        let page = ScanPage::from_json(&json!({"data": []}), &columns);
+        assert_eq!(page.total_count, None);
+    }
+}
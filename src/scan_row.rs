@@ -0,0 +1,231 @@
+use super::*;
+use std::time::SystemTime;
+
+/// A struct decoded from the values of a scanned symbol, usually implemented with `#[derive(ScanRow)]`
+/// of the `derive` feature.
+pub trait ScanRow: Sized {
+    /// Get the columns to request for the row.
+    /// Returns `Error::InvalidField` or `Error::InvalidInterval` if a column has an unknown field or interval,
+    /// or an interval on a field without interval.
+    fn columns() -> Result<Vec<FieldWithInterval>>;
+
+    /// Decodes a row from the values of a symbol, given the columns returned by `columns`.
+    /// Returns `Error::MissingField` or `Error::UnexpectedValue` if a column is missing or mistyped.
+    fn from_values(values: &TimedSymbolValues, columns: &[FieldWithInterval]) -> Result<Self>;
+}
+
+/// Types that can be decoded from the value of a column.
+/// Null is an unexpected value, unless decoded as an `Option`.
+pub trait FromScanValue: Sized {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self>;
+}
+
+fn required<T>(
+    value: Result<Option<T>>,
+    field: &FieldWithInterval,
+    expected: &'static str,
+) -> Result<T> {
+    value?.ok_or_else(|| Error::UnexpectedValue {
+        field: field.to_string(),
+        expected,
+        value: Value::Null.to_string(),
+    })
+}
+
+impl FromScanValue for f64 {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        required(values.get_f64(field), field, "a number")
+    }
+}
+
+impl FromScanValue for i64 {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        required(values.get_i64(field), field, "an integer")
+    }
+}
+
+impl FromScanValue for bool {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        required(values.get_bool(field), field, "a bool")
+    }
+}
+
+impl FromScanValue for String {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        required(values.get_str(field), field, "a string").map(|x| x.to_owned())
+    }
+}
+
+impl FromScanValue for SystemTime {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        required(values.get_timestamp(field), field, "a timestamp")
+    }
+}
+
+impl FromScanValue for Value {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        Ok(values.get_value(field)?.cloned().unwrap_or(Value::Null))
+    }
+}
+
+impl<T: FromScanValue> FromScanValue for Option<T> {
+    fn from_scan_value(values: &TimedSymbolValues, field: &FieldWithInterval) -> Result<Self> {
+        match values.get_value(field)? {
+            None => Ok(None),
+            Some(_) => T::from_scan_value(values, field).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockScanner;
+    use anyhow::Result;
+    use easytradeview_derive::ScanRow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, ScanRow)]
+    #[tv(interval = "60")]
+    struct Row {
+        #[tv(symbol)]
+        ticker: Ticker,
+        close: f64,
+        #[tv(field = "RSI", interval = "1d")]
+        rsi: Option<f64>,
+        #[tv(field = "type")]
+        symbol_type: String,
+        r#type: String,
+        #[tv(field = "pricescale")]
+        price_scale: i64,
+        fractional: bool,
+    }
+
+    #[derive(Debug, ScanRow)]
+    struct MistypedRow {
+        #[tv(field = "description")]
+        _description: f64,
+    }
+
+    #[derive(Debug, ScanRow)]
+    struct UnknownFieldRow {
+        #[tv(field = "unknown")]
+        _unknown: f64,
+    }
+
+    #[derive(Debug, ScanRow)]
+    #[tv(interval = "3h")]
+    struct UnknownIntervalRow {
+        #[tv(field = "close")]
+        _close: f64,
+    }
+
+    #[derive(Debug, ScanRow)]
+    struct TypeIntervalRow {
+        #[tv(field = "type", interval = "60")]
+        _symbol_type: String,
+    }
+
+    static COUNTED_COLUMNS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountedRow {
+        close: f64,
+    }
+
+    impl ScanRow for CountedRow {
+        fn columns() -> crate::Result<Vec<FieldWithInterval>> {
+            COUNTED_COLUMNS_CALLS.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![Field::Close.with_interval(&Interval::Day1)])
+        }
+
+        fn from_values(
+            values: &TimedSymbolValues,
+            columns: &[FieldWithInterval],
+        ) -> crate::Result<Self> {
+            let close = FromScanValue::from_scan_value(values, &columns[0])?;
+            Ok(Self { close })
+        }
+    }
+
+    #[test]
+    fn test_scan_row_columns() {
+        let columns: Vec<String> = Row::columns()
+            .unwrap()
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                "close|60",
+                "RSI",
+                "type",
+                "type",
+                "pricescale",
+                "fractional"
+            ]
+        );
+
+        assert!(matches!(
+            UnknownFieldRow::columns(),
+            Err(Error::InvalidField(_))
+        ));
+        assert!(matches!(
+            UnknownIntervalRow::columns(),
+            Err(Error::InvalidInterval(_))
+        ));
+        assert!(matches!(
+            TypeIntervalRow::columns(),
+            Err(Error::InvalidInterval(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scan_as() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "BINANCE");
+
+        let rows: Vec<Row> = tradingview
+            .scan_as(&["BTCUSDT", "OKX:BTCUSDT.P", "ETHBTC"])
+            .await?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].ticker, Ticker::new("OKX", "BTCUSDT.P"));
+        assert_eq!(rows[2].ticker, Ticker::new("BINANCE", "ETHBTC"));
+        assert_eq!(rows[0].ticker, Ticker::new("BINANCE", "BTCUSDT"));
+        assert!(rows[0].close > 0.0 && rows[0].rsi.is_some());
+        assert_eq!(rows[1].symbol_type, "swap");
+        assert_eq!(rows[1].r#type, "swap");
+        assert_eq!(rows[0].price_scale, 100);
+        assert!(!rows[0].fractional);
+        // The fixture has no RSI for BINANCE:ETHBTC.
+        assert_eq!(rows[2].rsi, None);
+
+        assert!(matches!(
+            tradingview.scan_as::<MistypedRow, _>(&["BTCUSDT"]).await,
+            Err(Error::UnexpectedValue {
+                expected: "a number",
+                ..
+            })
+        ));
+        assert!(matches!(
+            tradingview
+                .scan_as::<UnknownFieldRow, _>(&["BTCUSDT"])
+                .await,
+            Err(Error::InvalidField(_))
+        ));
+        assert!(matches!(
+            tradingview
+                .scan_as::<Row, _>(&["BTCUSDT", "NOPEUSDT", "ETHBTC"])
+                .await,
+            Err(Error::UnknownSymbol(x)) if x == "BINANCE:NOPEUSDT"
+        ));
+
+        let rows: Vec<CountedRow> = tradingview
+            .scan_as(&["BTCUSDT", "ETHUSDT", "ETHBTC"])
+            .await?;
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|x| x.close > 0.0));
+        assert_eq!(COUNTED_COLUMNS_CALLS.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+}
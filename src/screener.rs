@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
-use anyhow::{anyhow, Result};
+use crate::{Error, Result};
 use lazy_static::lazy_static;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Screener {
@@ -77,7 +78,7 @@ impl Screener {
         }
         MAP.get(s)
             .cloned()
-            .ok_or(anyhow!("Invalid screener: {}", s))
+            .ok_or_else(|| Error::InvalidScreener(s.to_owned()))
     }
 
     /// Get all screener
@@ -96,13 +97,26 @@ impl AsRef<str> for Screener {
             static ref MAP: HashMap<Screener, &'static str> =
                 SCREENER_STRINGS.iter().map(|x| (x.0, x.1)).collect();
         }
-        MAP.get(self).map(|x| *x).unwrap_or("")
+        MAP.get(self).copied().unwrap_or("")
     }
 }
 
-impl ToString for Screener {
-    fn to_string(&self) -> String {
-        self.as_ref().to_string()
+impl std::fmt::Display for Screener {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl Serialize for Screener {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for Screener {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Screener::parse(&s).map_err(de::Error::custom)
     }
 }
 
@@ -116,4 +130,14 @@ mod tests {
         assert!(Screener::parse("undefined").is_err());
         assert!(matches!(Screener::parse("all"), Ok(Screener::All)));
     }
+
+    #[test]
+    fn test_serde_screener() -> Result<(), serde_json::Error> {
+        for screener in Screener::all_screeners() {
+            let json = serde_json::to_string(screener)?;
+            assert_eq!(&serde_json::from_str::<Screener>(&json)?, screener);
+        }
+        assert!(serde_json::from_str::<Screener>(r#""undefined""#).is_err());
+        Ok(())
+    }
 }
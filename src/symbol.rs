@@ -1,11 +1,13 @@
 use super::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
 
 pub trait SymbolValues {
-    type Field: AsRef<Field> + Eq + PartialEq + std::hash::Hash + Clone;
+    type Field: AsRef<Field> + Eq + PartialEq + std::hash::Hash + Clone + std::fmt::Display;
 
-    /// Returns the symbol name.
-    fn symbol(&self) -> &str;
+    /// Returns the ticker of the symbol.
+    fn symbol(&self) -> &Ticker;
 
     /// Returns the symbol values.
     fn values(&self) -> &HashMap<Self::Field, Value>;
@@ -23,19 +25,99 @@ pub trait SymbolValues {
         }
         values
     }
+
+    /// Get the value of a field, `None` if the scanner returned null.
+    /// Returns `Error::MissingField` if there is no column for the field.
+    fn get_value(&self, field: &Self::Field) -> Result<Option<&Value>> {
+        match self.values().get(field) {
+            None => Err(Error::MissingField(field.to_string())),
+            Some(Value::Null) => Ok(None),
+            Some(value) => Ok(Some(value)),
+        }
+    }
+
+    /// Get the value of a field as f64, `None` if the scanner returned null.
+    /// Returns `Error::MissingField` if there is no column for the field,
+    /// and `Error::UnexpectedValue` if the value is not a number.
+    fn get_f64(&self, field: &Self::Field) -> Result<Option<f64>> {
+        get_as(self, field, "a number", Value::as_f64)
+    }
+
+    /// Get the value of a field as i64, `None` if the scanner returned null.
+    /// Numbers with a fractional part are unexpected values.
+    fn get_i64(&self, field: &Self::Field) -> Result<Option<i64>> {
+        get_as(self, field, "an integer", as_integer)
+    }
+
+    /// Get the value of a field as a bool, from either a JSON bool or `"true"` and `"false"`,
+    /// `None` if the scanner returned null.
+    fn get_bool(&self, field: &Self::Field) -> Result<Option<bool>> {
+        get_as(self, field, "a bool", as_bool)
+    }
+
+    /// Get the value of a field as a string, `None` if the scanner returned null.
+    fn get_str(&self, field: &Self::Field) -> Result<Option<&str>> {
+        get_as(self, field, "a string", Value::as_str)
+    }
+
+    /// Get the value of a field as a time, from unix time in seconds, `None` if the scanner returned null.
+    fn get_timestamp(&self, field: &Self::Field) -> Result<Option<SystemTime>> {
+        get_as(self, field, "a timestamp", |x| {
+            let secs = Duration::try_from_secs_f64(x.as_f64()?).ok()?;
+            SystemTime::UNIX_EPOCH.checked_add(secs)
+        })
+    }
+}
+
+/// Converts a value to an integer. Integers may be sent as floats, e.g. `100.0`.
+fn as_integer(value: &Value) -> Option<i64> {
+    value.as_i64().or_else(|| {
+        let f = value.as_f64()?;
+        (f.fract() == 0.0 && f.abs() < i64::MAX as f64).then_some(f as i64)
+    })
+}
+
+/// Converts a value to a bool. Bools may be sent as strings, e.g. `"true"`.
+fn as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(x) => Some(*x),
+        Value::String(x) => x.parse().ok(),
+        _ => None,
+    }
 }
 
-#[derive(Debug, Default)]
+/// Get the value of a field converted by `f`, `Error::UnexpectedValue` if it fails.
+fn get_as<'a, V, T, F>(
+    values: &'a V,
+    field: &V::Field,
+    expected: &'static str,
+    f: F,
+) -> Result<Option<T>>
+where
+    V: SymbolValues + ?Sized,
+    F: Fn(&'a Value) -> Option<T>,
+{
+    match values.get_value(field)? {
+        None => Ok(None),
+        Some(value) => f(value).map(Some).ok_or_else(|| Error::UnexpectedValue {
+            field: field.to_string(),
+            expected,
+            value: value.to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimpleSymbolValues {
-    symbol: String,
+    symbol: Ticker,
     values: HashMap<Field, Value>,
 }
 
 impl SimpleSymbolValues {
     /// Creates a new `SimpleSymbolValues` instance.
-    pub fn new(symbol: &str) -> Self {
+    pub fn new<T: ToTicker>(symbol: T) -> Self {
         Self {
-            symbol: symbol.to_owned(),
+            symbol: symbol.to_ticker(),
             values: HashMap::new(),
         }
     }
@@ -44,7 +126,7 @@ impl SimpleSymbolValues {
 impl SymbolValues for SimpleSymbolValues {
     type Field = Field;
 
-    fn symbol(&self) -> &str {
+    fn symbol(&self) -> &Ticker {
         &self.symbol
     }
 
@@ -57,28 +139,29 @@ impl SymbolValues for SimpleSymbolValues {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TimedSymbolValues {
-    symbol: String,
+    symbol: Ticker,
     values: HashMap<FieldWithInterval, Value>,
 }
 
 impl TimedSymbolValues {
     /// Creates a new `TimedSymbolValues` instance.
-    pub fn new(symbol: &str) -> Self {
+    pub fn new<T: ToTicker>(symbol: T) -> Self {
         Self {
-            symbol: symbol.to_owned(),
+            symbol: symbol.to_ticker(),
             values: HashMap::new(),
         }
     }
 
     /// Filters the values by interval, returning a `SimpleSymbolValues` instance.
+    /// Values of fields without interval are kept whatever the interval.
     pub fn filter_interval(&self, interval: Interval) -> SimpleSymbolValues {
         let mut simple_values = SimpleSymbolValues::new(&self.symbol);
         simple_values.values = self
             .values
             .iter()
-            .filter(|(k, _)| k.interval == interval)
+            .filter(|(k, _)| k.interval == interval || !k.field.has_interval())
             .map(|(k, v)| (k.field.clone(), v.clone()))
             .collect();
         simple_values
@@ -88,7 +171,7 @@ impl TimedSymbolValues {
 impl SymbolValues for TimedSymbolValues {
     type Field = FieldWithInterval;
 
-    fn symbol(&self) -> &str {
+    fn symbol(&self) -> &Ticker {
         &self.symbol
     }
 
@@ -100,3 +183,176 @@ impl SymbolValues for TimedSymbolValues {
         &mut self.values
     }
 }
+
+/// Canonical metadata of a symbol, as returned by `TradingView::resolve`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub ticker: Ticker,
+    pub exchange: String,
+    pub name: String,
+    pub symbol_type: String,
+    pub subtype: String,
+    pub description: String,
+    pub pricescale: Option<u64>,
+    pub minmov: Option<u64>,
+    pub fractional: bool,
+    pub currency: String,
+}
+
+impl SymbolInfo {
+    /// Get the fields retrieved to resolve a symbol.
+    pub fn fields() -> &'static [Field] {
+        static VALUES: [Field; 9] = [
+            Field::Exchange,
+            Field::Name,
+            Field::Type,
+            Field::SubType,
+            Field::Description,
+            Field::PriceScale,
+            Field::MinMov,
+            Field::Fractional,
+            Field::Currency,
+        ];
+        &VALUES
+    }
+
+    /// Creates a new `SymbolInfo` instance from the values of `SymbolInfo::fields()`.
+    pub fn from_values<V: SymbolValues>(values: &V) -> SymbolInfo {
+        let get = |field: &Field| {
+            values
+                .values()
+                .iter()
+                .find(|(k, _)| k.as_ref() == field)
+                .map(|(_, v)| v)
+                .unwrap_or(&Value::Null)
+        };
+        let get_str = |field: &Field| get(field).as_str().unwrap_or_default().to_owned();
+        let get_u64 = |field: &Field| as_integer(get(field)).and_then(|x| u64::try_from(x).ok());
+        SymbolInfo {
+            ticker: values.symbol().clone(),
+            exchange: get_str(&Field::Exchange),
+            name: get_str(&Field::Name),
+            symbol_type: get_str(&Field::Type),
+            subtype: get_str(&Field::SubType),
+            description: get_str(&Field::Description),
+            pricescale: get_u64(&Field::PriceScale),
+            minmov: get_u64(&Field::MinMov),
+            fractional: as_bool(get(&Field::Fractional)).unwrap_or(false),
+            currency: get_str(&Field::Currency),
+        }
+    }
+
+    /// Get the minimum price change, `minmov / pricescale`.
+    pub fn min_tick(&self) -> Option<f64> {
+        match (self.minmov, self.pricescale) {
+            (Some(minmov), Some(pricescale)) if pricescale > 0 => {
+                Some(minmov as f64 / pricescale as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_typed_values() {
+        let mut values = TimedSymbolValues::new("OKX:BTCUSDT");
+        let rsi = Field::RSI.with_interval(&Interval::Hour1);
+        let pricescale = Field::PriceScale.with_interval(&Interval::default());
+        let symbol_type = Field::Type.with_interval(&Interval::default());
+        let values_mut = values.values_mut();
+        values_mut.insert(rsi.clone(), Value::Null);
+        values_mut.insert(pricescale.clone(), json!(100.0));
+        values_mut.insert(symbol_type.clone(), json!("spot"));
+
+        assert!(matches!(values.get_f64(&rsi), Ok(None)));
+        assert!(matches!(
+            values.get_f64(&Field::RSI.with_interval(&Interval::Day1)),
+            Err(Error::MissingField(x)) if x == "RSI"
+        ));
+        assert_eq!(values.get_i64(&pricescale).ok(), Some(Some(100)));
+        assert_eq!(values.get_str(&symbol_type).ok(), Some(Some("spot")));
+        assert!(matches!(
+            values.get_f64(&symbol_type),
+            Err(Error::UnexpectedValue {
+                expected: "a number",
+                ..
+            })
+        ));
+        assert_eq!(
+            values.get_timestamp(&pricescale).ok(),
+            Some(Some(SystemTime::UNIX_EPOCH + Duration::from_secs(100)))
+        );
+
+        let fractional = Field::Fractional.with_interval(&Interval::default());
+        values
+            .values_mut()
+            .insert(fractional.clone(), json!("false"));
+        assert_eq!(values.get_bool(&fractional).ok(), Some(Some(false)));
+        assert!(matches!(
+            values.get_bool(&pricescale),
+            Err(Error::UnexpectedValue {
+                expected: "a bool",
+                ..
+            })
+        ));
+        assert_eq!(Field::Fractional.kind(), Some(ValueKind::Bool));
+        assert_eq!(Field::undefined("sector").kind(), None);
+    }
+
+    #[test]
+    fn test_symbol_info_from_float_values() {
+        let mut values = TimedSymbolValues::new("OKX:BTCUSDT");
+        let values_mut = values.values_mut();
+        for (field, value) in [
+            (Field::PriceScale, json!(100.0)),
+            (Field::MinMov, json!(1.0)),
+            (Field::Fractional, json!(true)),
+        ] {
+            values_mut.insert(field.with_interval(&Interval::default()), value);
+        }
+        let info = SymbolInfo::from_values(&values);
+        assert_eq!((info.pricescale, info.minmov), (Some(100), Some(1)));
+        assert_eq!(info.min_tick(), Some(0.01));
+        assert!(info.fractional);
+    }
+
+    #[test]
+    fn test_serde_symbol_values() -> Result<(), serde_json::Error> {
+        let mut values = TimedSymbolValues::new("OKX:BTCUSDT");
+        let values_mut = values.values_mut();
+        values_mut.insert(Field::Close.with_interval(&Interval::Hour1), json!(1.5));
+        values_mut.insert(
+            Field::Name.with_interval(&Interval::default()),
+            json!("BTCUSDT"),
+        );
+        values_mut.insert(
+            Field::undefined("sector").with_interval(&Interval::Hour1),
+            json!(2.5),
+        );
+        values_mut.insert(
+            Field::undefined_without_interval("sector").with_interval(&Interval::default()),
+            json!("Crypto"),
+        );
+        let json = serde_json::to_value(&values)?;
+        assert_eq!(
+            json,
+            json!({"symbol": "OKX:BTCUSDT", "values": {
+                "close|60": 1.5,
+                "name": "BTCUSDT",
+                "sector|60": 2.5,
+                "sector|": "Crypto",
+            }})
+        );
+        assert_eq!(serde_json::from_value::<TimedSymbolValues>(json)?, values);
+
+        let simple = values.filter_interval(Interval::Hour1);
+        let json = serde_json::to_string(&simple)?;
+        assert_eq!(serde_json::from_str::<SimpleSymbolValues>(&json)?, simple);
+        Ok(())
+    }
+}
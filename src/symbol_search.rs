@@ -0,0 +1,246 @@
+use super::*;
+use reqwest::header::{HeaderMap, HeaderValue, ORIGIN};
+use reqwest::{Method, Url};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SYMBOL_SEARCH_URL: &str = "https://symbol-search.tradingview.com/";
+
+/// A free-text symbol lookup, across exchanges and asset types unless restricted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolSearchQuery {
+    pub text: String,
+    pub exchange: Option<String>,
+    pub search_type: Option<String>,
+    pub start: usize,
+}
+
+impl SymbolSearchQuery {
+    /// Creates a new `SymbolSearchQuery` instance looking up `text`.
+    pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            text: text.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Restricts the lookup to an exchange, e.g. `BINANCE`.
+    pub fn exchange<S: AsRef<str>>(mut self, exchange: S) -> Self {
+        self.exchange = Some(exchange.as_ref().to_owned());
+        self
+    }
+
+    /// Restricts the lookup to an asset type, e.g. `crypto` or `stock`.
+    pub fn search_type<S: AsRef<str>>(mut self, search_type: S) -> Self {
+        self.search_type = Some(search_type.as_ref().to_owned());
+        self
+    }
+
+    /// Skips the first `start` results.
+    pub fn start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+}
+
+/// A symbol found by a `SymbolSearch`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolSearchResult {
+    pub ticker: Ticker,
+    pub description: String,
+    pub symbol_type: String,
+    pub exchange: String,
+    pub currency: String,
+    pub country: Option<String>,
+}
+
+impl SymbolSearchResult {
+    /// Parses a result of the symbol-search endpoint, removing the highlighting of the matched text.
+    pub fn from_json(json: &Value) -> SymbolSearchResult {
+        let get_str = |key: &str| strip_highlight(json[key].as_str().unwrap_or_default());
+        let exchange = get_str("exchange");
+        // `prefix` is the exchange of the ticker when it differs from the displayed exchange name.
+        let prefix = json["prefix"]
+            .as_str()
+            .map_or(exchange.clone(), |x| x.to_owned());
+        SymbolSearchResult {
+            ticker: Ticker::new(prefix, get_str("symbol")),
+            description: get_str("description"),
+            symbol_type: get_str("type"),
+            exchange,
+            currency: get_str("currency_code"),
+            country: json["country"].as_str().map(|x| x.to_owned()),
+        }
+    }
+}
+
+fn strip_highlight(s: &str) -> String {
+    s.replace("<em>", "").replace("</em>", "")
+}
+
+/// A page of symbol-search results, along with the number of results after it.
+#[derive(Debug, Default)]
+pub struct SymbolSearchPage {
+    pub symbols: Vec<SymbolSearchResult>,
+    pub symbols_remaining: usize,
+}
+
+/// A client of the TradingView symbol-search endpoint.
+#[derive(Debug, Clone)]
+pub struct SymbolSearch {
+    base_url: Url,
+    timeout: Option<Duration>,
+    transport: Arc<dyn Transport>,
+}
+
+impl Default for SymbolSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolSearch {
+    /// Creates a new `SymbolSearch` instance using `https://symbol-search.tradingview.com/`.
+    pub fn new() -> SymbolSearch {
+        Self::with_base_url(SYMBOL_SEARCH_URL).expect("default url is valid")
+    }
+
+    /// Creates a new `SymbolSearch` instance using another base URL, e.g. a local stand-in.
+    pub fn with_base_url<S: AsRef<str>>(base_url: S) -> Result<SymbolSearch> {
+        let mut base_url = base_url.as_ref().to_owned();
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+        Ok(SymbolSearch {
+            base_url: Url::parse(&base_url)?,
+            timeout: None,
+            transport: Arc::new(ReqwestTransport::default()),
+        })
+    }
+
+    /// Sets the timeout of each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Uses a custom transport to send requests. Defaults to `ReqwestTransport`.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Looks up symbols matching `query`, returning one page of results.
+    pub async fn search(&self, query: &SymbolSearchQuery) -> Result<SymbolSearchPage> {
+        let mut url = self.base_url.join("symbol_search/v3/")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("text", &query.text);
+            pairs.append_pair("hl", "0");
+            pairs.append_pair("exchange", query.exchange.as_deref().unwrap_or_default());
+            pairs.append_pair("lang", "en");
+            pairs.append_pair(
+                "search_type",
+                query.search_type.as_deref().unwrap_or_default(),
+            );
+            pairs.append_pair("start", &query.start.to_string());
+            pairs.append_pair("domain", "production");
+        }
+
+        // The endpoint rejects requests that don't come from the TradingView website.
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ORIGIN,
+            HeaderValue::from_static("https://www.tradingview.com"),
+        );
+        let request = TransportRequest {
+            method: Method::GET,
+            url,
+            headers,
+            body: None,
+            timeout: self.timeout,
+        };
+        let response = self.transport.send(request).await?;
+        if !response.status.is_success() {
+            return Err(Error::Http {
+                status: response.status.as_u16(),
+                body: String::from_utf8_lossy(&response.body).into_owned(),
+            });
+        }
+
+        let json: Value = serde_json::from_slice(&response.body)?;
+        let data_array: Vec<Value> = vec![];
+        let symbols = json["symbols"]
+            .as_array()
+            .unwrap_or(&data_array)
+            .iter()
+            .map(SymbolSearchResult::from_json)
+            .collect();
+        Ok(SymbolSearchPage {
+            symbols,
+            symbols_remaining: json["symbols_remaining"].as_u64().unwrap_or(0) as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockScanner;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[test]
+    fn test_search_result_from_json() {
+        let json = json!({
+            "symbol": "<em>BTC</em>USDT",
+            "description": "Bitcoin / TetherUS",
+            "type": "spot",
+            "exchange": "Binance",
+            "prefix": "BINANCE",
+            "currency_code": "USDT",
+        });
+        let result = SymbolSearchResult::from_json(&json);
+        assert_eq!(result.ticker, Ticker::new("BINANCE", "BTCUSDT"));
+        assert_eq!(result.exchange, "Binance");
+        assert_eq!(result.country, None);
+    }
+
+    #[tokio::test]
+    async fn test_symbol_search() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let search = mock.symbol_search();
+
+        let page = search
+            .search(&SymbolSearchQuery::new("bitcoin").exchange("BINANCE"))
+            .await?;
+        let tickers: Vec<String> = page.symbols.iter().map(|x| x.ticker.to_string()).collect();
+        assert_eq!(
+            tickers,
+            ["BINANCE:BTCUSDT", "BINANCE:ETHBTC", "BINANCE:BTCUSDT.P"]
+        );
+
+        let page = search
+            .search(&SymbolSearchQuery::new("aapl").search_type("stock"))
+            .await?;
+        assert_eq!(page.symbols.len(), 1);
+        assert_eq!(page.symbols[0].currency, "USD");
+        assert_eq!(page.symbols[0].country.as_deref(), Some("US"));
+
+        let page = search
+            .search(&SymbolSearchQuery::new("").exchange("BINANCE"))
+            .await?;
+        assert!(page.symbols_remaining > 0);
+        let rest = search
+            .search(
+                &SymbolSearchQuery::new("")
+                    .exchange("BINANCE")
+                    .start(page.symbols.len()),
+            )
+            .await?;
+        assert_eq!(rest.symbols.len(), page.symbols_remaining);
+        assert_eq!(rest.symbols_remaining, 0);
+        Ok(())
+    }
+}
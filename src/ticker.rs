@@ -0,0 +1,145 @@
+use crate::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+/// A symbol on a specific exchange, printed and parsed as `EXCHANGE:SYMBOL`.
+/// The exchange may be empty, in which case the default exchange of the `TradingView` client is used.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ticker {
+    pub exchange: String,
+    pub symbol: String,
+}
+
+impl Ticker {
+    /// Creates a new `Ticker` instance.
+    pub fn new<S1, S2>(exchange: S1, symbol: S2) -> Ticker
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        Self {
+            exchange: exchange.as_ref().to_owned(),
+            symbol: symbol.as_ref().to_owned(),
+        }
+    }
+
+    /// Parses a ticker in the form of `EXCHANGE:SYMBOL`.
+    pub fn parse(s: &str) -> Result<Ticker> {
+        match s.split_once(':') {
+            Some((exchange, symbol)) if !exchange.is_empty() && !symbol.is_empty() => {
+                Ok(Ticker::new(exchange, symbol))
+            }
+            _ => Err(Error::InvalidTicker(s.to_owned())),
+        }
+    }
+
+    /// Parses a ticker in the form of `EXCHANGE:SYMBOL`, or a bare `SYMBOL` without exchange.
+    pub fn parse_symbol(s: &str) -> Ticker {
+        Self::parse(s).unwrap_or(Ticker::new("", s))
+    }
+
+    /// Checks if the ticker has an exchange.
+    pub fn has_exchange(&self) -> bool {
+        !self.exchange.is_empty()
+    }
+
+    /// Returns the ticker, using `exchange` if it has none.
+    pub fn or_exchange(self, exchange: &str) -> Ticker {
+        if self.has_exchange() {
+            self
+        } else {
+            Ticker::new(exchange, self.symbol)
+        }
+    }
+}
+
+impl FromStr for Ticker {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ticker::parse(s)
+    }
+}
+
+impl std::fmt::Display for Ticker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.has_exchange() {
+            write!(f, "{}:{}", self.exchange, self.symbol)
+        } else {
+            write!(f, "{}", self.symbol)
+        }
+    }
+}
+
+/// Serializes as `EXCHANGE:SYMBOL`, or `SYMBOL` without exchange.
+impl Serialize for Ticker {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ticker {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Ticker::parse_symbol(&s))
+    }
+}
+
+/// Types that can be used as a ticker: `Ticker` itself, or strings such as `"BINANCE:BTCUSDT"` and `"BTCUSDT"`.
+pub trait ToTicker {
+    fn to_ticker(&self) -> Ticker;
+}
+
+impl ToTicker for Ticker {
+    fn to_ticker(&self) -> Ticker {
+        self.clone()
+    }
+}
+
+impl ToTicker for str {
+    fn to_ticker(&self) -> Ticker {
+        Ticker::parse_symbol(self)
+    }
+}
+
+impl ToTicker for String {
+    fn to_ticker(&self) -> Ticker {
+        Ticker::parse_symbol(self)
+    }
+}
+
+impl<T: ToTicker + ?Sized> ToTicker for &T {
+    fn to_ticker(&self) -> Ticker {
+        (**self).to_ticker()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ticker() {
+        assert!(Ticker::parse("BTCUSDT").is_err());
+        assert!(matches!(
+            Ticker::parse(":BTCUSDT"),
+            Err(Error::InvalidTicker(_))
+        ));
+        assert_eq!(
+            Ticker::parse("BINANCE:BTCUSDT").unwrap(),
+            Ticker::new("BINANCE", "BTCUSDT")
+        );
+        assert_eq!(Ticker::parse_symbol("BTCUSDT"), Ticker::new("", "BTCUSDT"));
+        assert_eq!("OKX:BTCUSDT.P".to_ticker(), Ticker::new("OKX", "BTCUSDT.P"));
+    }
+
+    #[test]
+    fn test_ticker_to_string() {
+        assert_eq!(Ticker::new("OKX", "BTCUSDT").to_string(), "OKX:BTCUSDT");
+        assert_eq!(Ticker::new("", "BTCUSDT").to_string(), "BTCUSDT");
+        assert_eq!(
+            Ticker::new("", "BTCUSDT").or_exchange("BYBIT"),
+            Ticker::new("BYBIT", "BTCUSDT")
+        );
+    }
+}
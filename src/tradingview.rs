@@ -1,29 +1,268 @@
 use super::*;
-use anyhow::{Context, Result};
-use reqwest::Url;
-use serde_json::Value;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Method, StatusCode, Url};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 const SCANNER_URL: &str = "https://scanner.tradingview.com/";
+const MAX_FIELDS_PER_REQUEST: usize = 100;
+const MAX_TICKERS_PER_REQUEST: usize = 500;
+const PARALLELISM: usize = 4;
 
-#[derive(Debug)]
+type SharedResponse = Shared<BoxFuture<'static, Result<Value>>>;
+
+/// A call waiting for a shared in-flight request. When the last call goes away, e.g. because it was
+/// cancelled, the request is removed from the in-flight requests, which cancels it.
+struct InFlightCaller<'a> {
+    in_flight: &'a Mutex<HashMap<String, SharedResponse>>,
+    key: String,
+    response: Option<SharedResponse>,
+}
+
+impl Drop for InFlightCaller<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        // Released under the lock, so that the last of several calls dropped at once sees no other call.
+        self.response.take();
+        if in_flight.get(&self.key).and_then(|x| x.strong_count()) == Some(1) {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TradingView {
     screener: String,
     exchange: String,
-    client: reqwest::Client,
+    scanner_url: Url,
+    timeout: Option<Duration>,
+    headers: HeaderMap,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cassette: Option<Arc<Cassette>>,
+    cache: Option<Arc<ResponseCache>>,
+    in_flight: Arc<Mutex<HashMap<String, SharedResponse>>>,
+    max_fields_per_request: usize,
+    max_tickers_per_request: usize,
+    parallelism: usize,
+    transport: Arc<dyn Transport>,
+}
+
+#[derive(Debug, Default)]
+pub struct TradingViewBuilder {
+    screener: String,
+    exchange: String,
+    scanner_url: Option<String>,
+    timeout: Option<Duration>,
+    headers: HeaderMap,
+    user_agent: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    requests_per_second: Option<f64>,
+    max_concurrent_requests: Option<usize>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cassette: Option<Cassette>,
+    cache: Option<ResponseCache>,
+    max_fields_per_request: Option<usize>,
+    max_tickers_per_request: Option<usize>,
+    parallelism: Option<usize>,
+    transport: Option<Arc<dyn Transport>>,
+}
+
+impl TradingViewBuilder {
+    /// Sets the screener, e.g. `crypto` or `america`. Required by scans, which fail with
+    /// `Error::InvalidScreener` without one.
+    pub fn screener<S: AsRef<str>>(mut self, screener: S) -> Self {
+        self.screener = screener.as_ref().to_lowercase();
+        self
+    }
+
+    /// Sets the default exchange, used for symbols given without one.
+    pub fn exchange<S: AsRef<str>>(mut self, exchange: S) -> Self {
+        self.exchange = exchange.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the base URL of the scanner, defaults to `https://scanner.tradingview.com/`.
+    pub fn scanner_url<S: AsRef<str>>(mut self, scanner_url: S) -> Self {
+        self.scanner_url = Some(scanner_url.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the timeout of each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the headers sent with each request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request.
+    pub fn user_agent<S: AsRef<str>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the policy for retrying failed requests, defaults to `RetryPolicy::none()`.
+    /// `build` fails with `Error::InvalidRetryPolicy` if its multiplier is negative or not finite.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limits the number of requests per second, shared by all clones of the instance.
+    /// `build` fails with `Error::InvalidRateLimit` unless it is a positive finite number.
+    pub fn requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.requests_per_second = Some(requests_per_second);
+        self
+    }
+
+    /// Limits the number of concurrent requests, shared by all clones of the instance.
+    /// `build` fails with `Error::InvalidRateLimit` if it is 0.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Uses an existing rate limiter, e.g. to share it with other instances.
+    /// Overrides `requests_per_second` and `max_concurrent_requests`.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Records responses to, or replays responses from, a cassette file.
+    /// A recording cassette is written by `Cassette::save`, or when the last clone of the instance is dropped.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Caches the values retrieved for given symbols, shared by all clones of the instance.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets the maximum number of fields per request, larger field lists are split into several requests.
+    /// Defaults to 100.
+    pub fn max_fields_per_request(mut self, max_fields_per_request: usize) -> Self {
+        self.max_fields_per_request = Some(max_fields_per_request.max(1));
+        self
+    }
+
+    /// Sets the maximum number of tickers per scan request, larger ticker lists are split into several requests.
+    /// Defaults to 500.
+    pub fn max_tickers_per_request(mut self, max_tickers_per_request: usize) -> Self {
+        self.max_tickers_per_request = Some(max_tickers_per_request.max(1));
+        self
+    }
+
+    /// Sets the number of chunks of a split request that run concurrently, defaults to 4.
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = Some(parallelism.max(1));
+        self
+    }
+
+    /// Uses a pre-built reqwest::Client. Timeout and headers of the builder still apply to each request.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(client)));
+        self
+    }
+
+    /// Uses a custom transport to send requests, e.g. an in-process fake. Defaults to `ReqwestTransport`.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Builds the TradingView instance.
+    pub fn build(self) -> Result<TradingView> {
+        let mut scanner_url = self.scanner_url.unwrap_or(SCANNER_URL.to_owned());
+        if !scanner_url.ends_with('/') {
+            scanner_url.push('/');
+        }
+        let scanner_url = Url::parse(&scanner_url)?;
+
+        let mut headers = self.headers;
+        if let Some(user_agent) = self.user_agent {
+            let user_agent = HeaderValue::from_str(&user_agent)
+                .map_err(|_| Error::InvalidHeader(user_agent.clone()))?;
+            headers.insert(USER_AGENT, user_agent);
+        }
+
+        let retry_policy = match self.retry_policy {
+            Some(retry_policy) => {
+                let multiplier = retry_policy.multiplier;
+                retry_policy.multiplier(multiplier)?
+            }
+            None => RetryPolicy::none(),
+        };
+
+        let rate_limiter = match self.rate_limiter {
+            Some(rate_limiter) => Some(rate_limiter),
+            None if self.requests_per_second.is_none()
+                && self.max_concurrent_requests.is_none() =>
+            {
+                None
+            }
+            None => {
+                let mut rate_limiter = RateLimiter::new();
+                if let Some(requests_per_second) = self.requests_per_second {
+                    rate_limiter = rate_limiter.requests_per_second(requests_per_second)?;
+                }
+                if let Some(max_concurrent_requests) = self.max_concurrent_requests {
+                    rate_limiter = rate_limiter.max_concurrent_requests(max_concurrent_requests)?;
+                }
+                Some(Arc::new(rate_limiter))
+            }
+        };
+
+        Ok(TradingView {
+            screener: self.screener,
+            exchange: self.exchange,
+            scanner_url,
+            timeout: self.timeout,
+            headers,
+            retry_policy,
+            rate_limiter,
+            cassette: self.cassette.map(Arc::new),
+            cache: self.cache.map(Arc::new),
+            in_flight: Default::default(),
+            max_fields_per_request: self
+                .max_fields_per_request
+                .unwrap_or(MAX_FIELDS_PER_REQUEST),
+            max_tickers_per_request: self
+                .max_tickers_per_request
+                .unwrap_or(MAX_TICKERS_PER_REQUEST),
+            parallelism: self.parallelism.unwrap_or(PARALLELISM),
+            transport: self
+                .transport
+                .unwrap_or_else(|| Arc::new(ReqwestTransport::default())),
+        })
+    }
 }
 
 impl TradingView {
     /// Creates a new TradingView instance.
+    /// `exchange` is the default exchange, used for symbols given without one.
     pub fn new<S1, S2>(screener: S1, exchange: S2) -> TradingView
     where
         S1: AsRef<str>,
         S2: AsRef<str>,
     {
-        Self {
-            screener: screener.as_ref().to_lowercase(),
-            exchange: exchange.as_ref().to_owned(),
-            client: reqwest::Client::new(),
-        }
+        Self::builder()
+            .screener(screener)
+            .exchange(exchange)
+            .build()
+            .expect("default options are valid")
     }
 
     /// Creates a new TradingView instance with a custom reqwest::Client.
@@ -36,94 +275,301 @@ impl TradingView {
         S1: AsRef<str>,
         S2: AsRef<str>,
     {
-        Self {
-            screener: screener.as_ref().to_lowercase(),
-            exchange: exchange.as_ref().to_owned(),
-            client,
+        Self::builder()
+            .screener(screener)
+            .exchange(exchange)
+            .client(client)
+            .build()
+            .expect("default options are valid")
+    }
+
+    /// Creates a builder to configure a TradingView instance.
+    pub fn builder() -> TradingViewBuilder {
+        TradingViewBuilder::default()
+    }
+
+    /// Get the rate limiter, if any, e.g. to report its queue depth.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Creates a request to the scanner, with the configured timeout and headers.
+    fn request(&self, method: Method, url: Url, body: Option<&Value>) -> Result<TransportRequest> {
+        let mut headers = self.headers.clone();
+        let body = match body {
+            Some(body) => {
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                Some(serde_json::to_vec(body)?)
+            }
+            None => None,
+        };
+        Ok(TransportRequest {
+            method,
+            url,
+            headers,
+            body,
+            timeout: self.timeout,
+        })
+    }
+
+    /// Get the cassette, if any.
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
+    /// Get the response cache, if any, e.g. to report its hits and misses.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
+    /// Sends a request to the scanner and parses the JSON response.
+    /// Concurrent calls with the same normalized request share a single in-flight request, and all receive its result.
+    /// The request is cancelled once every call sharing it is cancelled.
+    async fn send(&self, method: Method, url: Url, body: Option<&Value>) -> Result<Value> {
+        let key = Cassette::request(&method, &self.scanner_url, &url, body).to_string();
+        let mut caller = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let response = match in_flight.get(&key) {
+                Some(response) => response.clone(),
+                None => {
+                    let this = self.clone();
+                    let body = body.cloned();
+                    let response = {
+                        let key = key.clone();
+                        async move {
+                            let response = this.replay_or_fetch(method, url, body.as_ref()).await;
+                            this.in_flight.lock().unwrap().remove(&key);
+                            response
+                        }
+                    }
+                    .boxed()
+                    .shared();
+                    in_flight.insert(key.clone(), response.clone());
+                    response
+                }
+            };
+            InFlightCaller {
+                in_flight: &self.in_flight,
+                key,
+                response: Some(response),
+            }
+        };
+        caller.response.as_mut().expect("response is set").await
+    }
+
+    /// Sends a request to the scanner and parses the JSON response.
+    /// With a cassette, the response is either replayed from it or recorded to it.
+    async fn replay_or_fetch(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        let Some(cassette) = &self.cassette else {
+            return self.fetch(method, url, body).await;
+        };
+        let request = Cassette::request(&method, &self.scanner_url, &url, body);
+        match cassette.mode() {
+            CassetteMode::Replay => cassette.get(&request),
+            CassetteMode::Record => {
+                let response = self.fetch(method, url, body).await?;
+                cassette.insert(request, response.clone());
+                Ok(response)
+            }
+        }
+    }
+
+    /// Sends a request to the scanner and parses the JSON response, retrying according to the retry policy.
+    /// Each attempt waits for the rate limiter, if any.
+    async fn fetch(&self, method: Method, url: Url, body: Option<&Value>) -> Result<Value> {
+        let mut attempt = 1;
+        loop {
+            let delay = {
+                let _permit = match &self.rate_limiter {
+                    Some(rate_limiter) => Some(rate_limiter.acquire().await),
+                    None => None,
+                };
+
+                let request = self.request(method.clone(), url.clone(), body)?;
+                match self.transport.send(request).await {
+                    Ok(response) => {
+                        let status = response.status;
+                        if status.is_success() {
+                            return Ok(serde_json::from_slice(&response.body)?);
+                        }
+                        let retry_after = RetryPolicy::retry_after(&response.headers);
+                        if self.retry_policy.should_retry(attempt)
+                            && RetryPolicy::is_retryable_status(status)
+                        {
+                            self.retry_policy.retry_delay(attempt, retry_after)
+                        } else if status == StatusCode::TOO_MANY_REQUESTS {
+                            return Err(Error::RateLimited { retry_after });
+                        } else {
+                            return Err(Error::Http {
+                                status: status.as_u16(),
+                                body: String::from_utf8_lossy(&response.body).into_owned(),
+                            });
+                        }
+                    }
+                    Err(err) => {
+                        if self.retry_policy.should_retry(attempt) && err.is_transient() {
+                            self.retry_policy.delay(attempt)
+                        } else {
+                            return Err(err);
+                        }
+                    }
+                }
+            };
+
+            // Wait without holding the rate limiter permit.
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Returns the ticker, using the default exchange if it has none.
+    fn ticker<T: ToTicker>(&self, symbol: T) -> Ticker {
+        symbol.to_ticker().or_exchange(&self.exchange)
+    }
+
+    /// Fills `values` with the cached values of `fields`, returning the fields missing from the cache.
+    fn cached_values(
+        &self,
+        symbol: &Ticker,
+        fields: &[FieldWithInterval],
+        values: &mut HashMap<FieldWithInterval, Value>,
+    ) -> Vec<FieldWithInterval> {
+        let Some(cache) = &self.cache else {
+            return fields.to_vec();
+        };
+        let mut missing = Vec::new();
+        for field in fields {
+            match cache.get(symbol, field) {
+                Some(value) => {
+                    values.insert(field.clone(), value);
+                }
+                None => missing.push(field.clone()),
+            }
+        }
+        missing
+    }
+
+    /// Caches the values retrieved for a symbol.
+    fn cache_values(&self, symbol: &Ticker, values: &HashMap<FieldWithInterval, Value>) {
+        if let Some(cache) = &self.cache {
+            for (field, value) in values {
+                cache.insert(symbol, field, value.clone());
+            }
+        }
+    }
+
+    /// Checks that the scanner returned values for a symbol.
+    /// Returns `Error::UnknownSymbol` if it returned no field at all, and `Error::NoData` if every value is null.
+    fn check_symbol_values(symbol_values: TimedSymbolValues) -> Result<TimedSymbolValues> {
+        if symbol_values.values().is_empty() {
+            Err(Error::UnknownSymbol(symbol_values.symbol().to_string()))
+        } else if symbol_values.values().values().all(|x| x.is_null()) {
+            Err(Error::NoData(symbol_values.symbol().to_string()))
+        } else {
+            Ok(symbol_values)
         }
     }
 
     /// Retrieves the specified fields for a given symbol, with all fields sharing the same interval.
-    pub async fn get_symbol_fields<S1, S2>(
+    /// Returns `Error::InvalidInterval` if the interval is not a known one,
+    /// and `Error::UnknownSymbol` or `Error::NoData` if the scanner has no value for the symbol.
+    pub async fn get_symbol_fields<T, S>(
         &self,
-        symbol: S1,
-        interval: S2,
+        symbol: T,
+        interval: S,
         fields: &[Field],
     ) -> Result<SimpleSymbolValues>
     where
-        S1: AsRef<str>,
-        S2: AsRef<str>,
+        T: ToTicker,
+        S: AsRef<str>,
     {
-        let mut url = Url::parse(SCANNER_URL)?.join("symbol")?;
-
-        let symbol = self.exchange.clone() + ":" + symbol.as_ref();
-        let interval = Interval::parse_undefined(interval.as_ref());
-        let fields: Vec<String> = fields
+        let interval = Interval::parse(interval.as_ref())?;
+        let fields: Vec<FieldWithInterval> = fields
             .iter()
-            .map(|x| x.to_string_with_interval(&interval))
+            .map(|x| x.clone().with_interval(&interval))
             .collect();
 
-        {
-            let mut query = url.query_pairs_mut();
-            query.append_pair("symbol", &symbol);
-            query.append_pair("fields", &fields.join(","));
-            query.append_pair("no_404", "true");
-        }
-
-        let response = self.client.get(url).send().await?;
-        if let Err(err) = response.error_for_status_ref() {
-            return Err(err).context(response.text().await?);
-        }
-
-        let mut symbol_values: SimpleSymbolValues = SimpleSymbolValues::new(symbol.as_str());
-        let json: Value = response.json().await.context(format!("Parse json error"))?;
-        if let Some(obj) = json.as_object() {
-            for (k, v) in obj {
-                let (field, _) = Field::parse_undefined_with_interval(k);
-                symbol_values.values_mut().insert(field, v.clone());
-            }
+        let values = self
+            .get_symbol_fields_with_interval(symbol, &fields)
+            .await?;
+        let mut symbol_values = SimpleSymbolValues::new(values.symbol());
+        for (k, v) in values.values() {
+            symbol_values
+                .values_mut()
+                .insert(k.field.clone(), v.clone());
         }
         Ok(symbol_values)
     }
 
     /// Retrieves the specified fields for a given symbol, each field with its own interval.
-    pub async fn get_symbol_fields_with_interval<S>(
+    /// With a cache, only the fields missing from it are requested.
+    /// Large field lists are split into several concurrent requests.
+    /// Returns `Error::UnknownSymbol` or `Error::NoData` if the scanner has no value for the symbol.
+    pub async fn get_symbol_fields_with_interval<T>(
         &self,
-        symbol: S,
+        symbol: T,
         fields: &[FieldWithInterval],
     ) -> Result<TimedSymbolValues>
     where
-        S: AsRef<str>,
+        T: ToTicker,
     {
-        let mut url = Url::parse(SCANNER_URL)?.join("symbol")?;
+        let symbol = self.ticker(symbol);
+        let mut symbol_values = TimedSymbolValues::new(&symbol);
+        let missing = self.cached_values(&symbol, fields, symbol_values.values_mut());
 
-        let symbol = self.exchange.clone() + ":" + symbol.as_ref();
-        let fields: Vec<String> = fields.iter().map(|x| x.to_string()).collect();
+        let chunks: Vec<HashMap<FieldWithInterval, Value>> =
+            stream::iter(missing.chunks(self.max_fields_per_request))
+                .map(|chunk| self.fetch_symbol_fields(&symbol, chunk))
+                .buffered(self.parallelism)
+                .try_collect()
+                .await?;
+        for values in chunks {
+            self.cache_values(&symbol, &values);
+            symbol_values.values_mut().extend(values);
+        }
+        if fields.is_empty() && symbol_values.values().is_empty() {
+            return Ok(symbol_values);
+        }
+        Self::check_symbol_values(symbol_values)
+    }
 
+    /// Requests the specified fields for a given symbol in a single request.
+    async fn fetch_symbol_fields(
+        &self,
+        symbol: &Ticker,
+        fields: &[FieldWithInterval],
+    ) -> Result<HashMap<FieldWithInterval, Value>> {
+        let mut url = self.scanner_url.join("symbol")?;
+        let columns: HashMap<String, &FieldWithInterval> =
+            fields.iter().map(|x| (x.to_string(), x)).collect();
         {
+            let columns: Vec<String> = fields.iter().map(|x| x.to_string()).collect();
             let mut query = url.query_pairs_mut();
-            query.append_pair("symbol", &symbol);
-            query.append_pair("fields", &fields.join(","));
+            query.append_pair("symbol", &symbol.to_string());
+            query.append_pair("fields", &columns.join(","));
             query.append_pair("no_404", "true");
         }
 
-        let response = self.client.get(url).send().await?;
-        if let Err(err) = response.error_for_status_ref() {
-            return Err(err).context(response.text().await?);
-        }
-
-        let mut symbol_values = TimedSymbolValues::new(symbol.as_str());
-        let json: Value = response.json().await.context(format!("Parse json error"))?;
+        let json = self.send(Method::GET, url, None).await?;
+        let mut values = HashMap::new();
         if let Some(obj) = json.as_object() {
             for (k, v) in obj {
-                let field = FieldWithInterval::parse_undefined(k);
-                symbol_values.values_mut().insert(field, v.clone());
+                let field = columns
+                    .get(k.as_str())
+                    .map_or_else(|| FieldWithInterval::parse_undefined(k), |x| (*x).clone());
+                values.insert(field, v.clone());
             }
         }
-        Ok(symbol_values)
+        Ok(values)
     }
 
-    /// Searches for symbols by given exchanges and types. Retrieves basic fields along with specified extra fields.
+    /// Searches for symbols by given exchanges, types and name. Retrieves basic fields along with specified extra fields.
+    /// Scans with other filters go through `scan` with a `ScanQuery`.
     pub async fn search_symbols<S1, S2, S3>(
         &self,
         exchanges: &[S1],
@@ -136,9 +582,6 @@ impl TradingView {
         S2: AsRef<str>,
         S3: AsRef<str>,
     {
-        let url = Url::parse(SCANNER_URL)?
-            .join(&format!("{}/", self.screener))?
-            .join("scan")?;
         let fields: Vec<FieldWithInterval> = [
             Field::Exchange,
             Field::Name,
@@ -151,117 +594,352 @@ impl TradingView {
         .chain(extra_fields.iter().cloned())
         .collect();
 
-        let types: Vec<&str> = types.iter().map(|x| x.as_ref()).collect();
-        let exchanges: Vec<&str> = exchanges.iter().map(|x| x.as_ref()).collect();
-        let columns: Vec<String> = fields.iter().map(|x| x.to_string()).collect();
-        let name_filter: &str = name_filter.as_ref();
-
-        let data = serde_json::json!({
-            "symbols": {
-                "tickers": [],
-                "query": {
-                    "types": types,
-                    "exchanges": exchanges,
+        let name = Field::Name.with_interval(&Interval::default());
+        let query = ScanQuery::new(&fields)
+            .exchanges(exchanges)
+            .types(types)
+            .filter(Filter::matches(name, name_filter));
+        Ok(self.scan(&query).await?.symbols)
+    }
+
+    /// Retrieves the specified fields for the given symbols, each field with its own interval.
+    /// Symbols are returned once each and in the given order, without the ones unknown to the scanner.
+    /// With a cache, only the fields missing from it are requested.
+    /// Large ticker and field lists are split into several concurrent requests.
+    pub async fn scan_symbols<T>(
+        &self,
+        symbols: &[T],
+        fields: &[FieldWithInterval],
+    ) -> Result<Vec<TimedSymbolValues>>
+    where
+        T: ToTicker,
+    {
+        let mut indexes: HashMap<Ticker, usize> = HashMap::new();
+        let mut tickers: Vec<Ticker> = Vec::new();
+        for ticker in symbols.iter().map(|x| self.ticker(x)) {
+            if !indexes.contains_key(&ticker) {
+                indexes.insert(ticker.clone(), tickers.len());
+                tickers.push(ticker);
+            }
+        }
+        if self.cache.is_some() {
+            return self.scan_symbols_cached(&tickers, fields).await;
+        }
+        let mut symbols = self.scan_tickers(&tickers, fields).await?;
+        // The scanner returns the symbols in an order of its own.
+        symbols.sort_by_key(|x| indexes.get(x.symbol()).copied().unwrap_or(usize::MAX));
+        Ok(symbols)
+    }
+
+    /// Retrieves the columns of `R` for the given symbols and decodes each symbol into a row, in the given order.
+    /// Returns the error of the first symbol failing as in `scan_symbols_batch`,
+    /// `Error::MissingField` or `Error::UnexpectedValue` if a column is missing or mistyped,
+    /// and the errors of `ScanRow::columns`.
+    pub async fn scan_as<R, T>(&self, symbols: &[T]) -> Result<Vec<R>>
+    where
+        R: ScanRow,
+        T: ToTicker,
+    {
+        let columns = R::columns()?;
+        self.scan_symbols_batch(symbols, &columns)
+            .await?
+            .into_iter()
+            .map(|x| R::from_values(&x?, &columns))
+            .collect()
+    }
+
+    /// Scans the given tickers, splitting the tickers and fields into chunks and merging the results per ticker.
+    async fn scan_tickers(
+        &self,
+        tickers: &[Ticker],
+        fields: &[FieldWithInterval],
+    ) -> Result<Vec<TimedSymbolValues>> {
+        // Empty lists still make one request, scanning every symbol or no column.
+        let ticker_chunks: Vec<&[Ticker]> = if tickers.is_empty() {
+            vec![tickers]
+        } else {
+            tickers.chunks(self.max_tickers_per_request).collect()
+        };
+        let field_chunks: Vec<&[FieldWithInterval]> = if fields.is_empty() {
+            vec![fields]
+        } else {
+            fields.chunks(self.max_fields_per_request).collect()
+        };
+        let queries = ticker_chunks.into_iter().flat_map(|tickers| {
+            field_chunks
+                .iter()
+                .map(move |fields| ScanQuery::new(fields).tickers(tickers))
+        });
+
+        let pages: Vec<ScanPage> = stream::iter(queries)
+            .map(|query| async move { self.scan(&query).await })
+            .buffered(self.parallelism)
+            .try_collect()
+            .await?;
+
+        let mut symbols: Vec<TimedSymbolValues> = Vec::new();
+        let mut indexes: HashMap<Ticker, usize> = HashMap::new();
+        for mut symbol_values in pages.into_iter().flat_map(|x| x.symbols) {
+            match indexes.get(symbol_values.symbol()) {
+                Some(&index) => {
+                    let values = std::mem::take(symbol_values.values_mut());
+                    symbols[index].values_mut().extend(values);
                 }
-            },
-            "filter": [
-                {
-                    "left": "name",
-                    "operation": "match",
-                    "right": name_filter,
+                None => {
+                    indexes.insert(symbol_values.symbol().clone(), symbols.len());
+                    symbols.push(symbol_values);
                 }
-            ],
-            "columns": columns
-        });
+            }
+        }
+        Ok(symbols)
+    }
 
-        let response = self.client.post(url).json(&data).send().await?;
-        if let Err(err) = response.error_for_status_ref() {
-            return Err(err).context(response.text().await?);
-        }
-
-        let mut symbol_values_vec: Vec<TimedSymbolValues> = Vec::new();
-        let json_body: serde_json::Value = response.json().await?;
-        let data_array: Vec<Value> = vec![];
-        let data_array = json_body["data"].as_array().unwrap_or(&data_array);
-        for data in data_array.iter() {
-            if let Some(symbol) = data["s"].as_str() {
-                let mut symbol_values = TimedSymbolValues::new(symbol);
-                if let Some(arr) = data["d"].as_array() {
-                    for (i, v) in arr.iter().enumerate() {
-                        symbol_values
-                            .values_mut()
-                            .insert(fields[i].clone(), v.to_owned());
+    /// Retrieves the specified fields for the given distinct symbols, scanning only the fields missing from the cache.
+    /// Symbols are returned in the given order, without the ones unknown to the scanner.
+    async fn scan_symbols_cached(
+        &self,
+        tickers: &[Ticker],
+        fields: &[FieldWithInterval],
+    ) -> Result<Vec<TimedSymbolValues>> {
+        let mut symbols: Vec<TimedSymbolValues> = Vec::new();
+        let mut missing_tickers: Vec<Ticker> = Vec::new();
+        let mut missing_fields: Vec<FieldWithInterval> = Vec::new();
+        let mut missing_field_set: HashSet<FieldWithInterval> = HashSet::new();
+        for ticker in tickers {
+            let mut symbol_values = TimedSymbolValues::new(ticker);
+            let missing = self.cached_values(ticker, fields, symbol_values.values_mut());
+            if !missing.is_empty() {
+                missing_tickers.push(ticker.clone());
+                for field in missing {
+                    if missing_field_set.insert(field.clone()) {
+                        missing_fields.push(field);
                     }
                 }
-                symbol_values_vec.push(symbol_values);
             }
+            symbols.push(symbol_values);
+        }
+        if missing_tickers.is_empty() {
+            return Ok(symbols);
         }
-        Ok(symbol_values_vec)
+
+        let missing_ticker_set: HashSet<&Ticker> = missing_tickers.iter().collect();
+        let mut found: HashMap<Ticker, TimedSymbolValues> = self
+            .scan_tickers(&missing_tickers, &missing_fields)
+            .await?
+            .into_iter()
+            .map(|x| (x.symbol().clone(), x))
+            .collect();
+        symbols.retain_mut(|symbol_values| {
+            let ticker = symbol_values.symbol().clone();
+            if !missing_ticker_set.contains(&ticker) {
+                return true;
+            }
+            let Some(fetched) = found.remove(&ticker) else {
+                return false;
+            };
+            self.cache_values(&ticker, fetched.values());
+            for (k, v) in fetched.values() {
+                symbol_values
+                    .values_mut()
+                    .entry(k.clone())
+                    .or_insert_with(|| v.clone());
+            }
+            true
+        });
+        Ok(symbols)
     }
 
-    /// Retrieves the specified fields for the given symbols, each field with its own interval.
-    pub async fn scan_symbols<S>(
+    /// Retrieves the specified fields for the given symbols, returning one result per symbol in the given order:
+    /// the values, `Error::UnknownSymbol` if the scanner doesn't know the symbol,
+    /// or `Error::NoData` if it has no value for any of the fields.
+    /// The outer error is returned when the request itself fails.
+    pub async fn scan_symbols_batch<T>(
         &self,
-        symbols: &[S],
+        symbols: &[T],
         fields: &[FieldWithInterval],
-    ) -> Result<Vec<TimedSymbolValues>>
+    ) -> Result<Vec<Result<TimedSymbolValues>>>
     where
-        S: AsRef<str>,
+        T: ToTicker,
     {
-        let url = Url::parse(SCANNER_URL)?
-            .join(&format!("{}/", self.screener))?
-            .join("scan")?;
+        let tickers: Vec<Ticker> = symbols.iter().map(|x| self.ticker(x)).collect();
+        let found: HashMap<Ticker, TimedSymbolValues> = self
+            .scan_symbols(&tickers, fields)
+            .await?
+            .into_iter()
+            .map(|x| (x.symbol().clone(), x))
+            .collect();
+        let results = tickers
+            .iter()
+            .map(|ticker| match found.get(ticker) {
+                Some(symbol_values) if fields.is_empty() => Ok(symbol_values.clone()),
+                Some(symbol_values) => Self::check_symbol_values(symbol_values.clone()),
+                None => Err(Error::UnknownSymbol(ticker.to_string())),
+            })
+            .collect();
+        Ok(results)
+    }
 
-        let tickers: Vec<String> = symbols
+    /// Checks that a symbol exists on the screener and retrieves its canonical metadata.
+    /// Returns `Error::UnknownSymbol` if the screener doesn't know the symbol.
+    pub async fn resolve<T: ToTicker>(&self, symbol: T) -> Result<SymbolInfo> {
+        let fields: Vec<FieldWithInterval> = SymbolInfo::fields()
             .iter()
-            .map(|x| self.exchange.clone() + ":" + x.as_ref())
+            .map(|x| x.clone().with_interval(&Interval::default()))
             .collect();
-        let columns: Vec<String> = fields.iter().map(|x| x.to_string()).collect();
+        let mut results = self.scan_symbols_batch(&[symbol], &fields).await?;
+        let values = results.remove(0)?;
+        Ok(SymbolInfo::from_values(&values))
+    }
 
-        let data = serde_json::json!({
-            "symbols": {
-                "tickers": tickers,
-                "query": {
-                }
-            },
-            "columns": columns
-        });
+    /// Get the URL of an endpoint of a screener, below the scanner URL.
+    fn screener_url(&self, screener: &str, endpoint: &str) -> Result<Url> {
+        if screener.is_empty() {
+            return Err(Error::InvalidScreener(screener.to_owned()));
+        }
+        Ok(self
+            .scanner_url
+            .join(&format!("{}/", screener))?
+            .join(endpoint)?)
+    }
 
-        let response = self.client.post(url).json(&data).send().await?;
-        if let Err(err) = response.error_for_status_ref() {
-            return Err(err).context(response.text().await?);
-        }
-
-        let mut symbol_values_vec: Vec<TimedSymbolValues> = Vec::new();
-        let json_body: serde_json::Value = response.json().await?;
-        let data_array: Vec<Value> = vec![];
-        let data_array = json_body["data"].as_array().unwrap_or(&data_array);
-        for data in data_array.iter() {
-            if let Some(symbol) = data["s"].as_str() {
-                let mut symbol_values = TimedSymbolValues::new(symbol);
-                if let Some(arr) = data["d"].as_array() {
-                    for (i, v) in arr.iter().enumerate() {
-                        symbol_values
-                            .values_mut()
-                            .insert(fields[i].clone(), v.to_owned());
-                    }
+    /// Runs a scan query on the screener, returning one page of results along with the total count.
+    /// Tickers of the query without exchange use the default exchange.
+    /// Returns `Error::InvalidScreener` if the instance was built without a screener.
+    pub async fn scan(&self, query: &ScanQuery) -> Result<ScanPage> {
+        let url = self.screener_url(&self.screener, "scan")?;
+
+        let mut query = query.clone();
+        query.tickers = query.tickers.iter().map(|x| self.ticker(x)).collect();
+
+        let json_body = self.send(Method::POST, url, Some(&query.to_json())).await?;
+        Ok(ScanPage::from_json(&json_body, &query.columns))
+    }
+
+    /// Retrieves the catalog of the columns supported by a screener, e.g. `crypto` or `america`.
+    pub async fn metainfo<S: AsRef<str>>(&self, screener: S) -> Result<Vec<FieldInfo>> {
+        let url = self.screener_url(&screener.as_ref().to_lowercase(), "metainfo")?;
+        let json_body = self.send(Method::POST, url, Some(&json!({}))).await?;
+        Ok(FieldInfo::from_metainfo(&json_body))
+    }
+
+    /// Runs a scan query page by page, yielding every matching symbol until the total count is exhausted,
+    /// or, if the scanner doesn't report it, until a page comes back short.
+    /// The range of `query`, if set, bounds the symbols yielded; each request fetches at most `page_size` symbols.
+    /// A `page_size` of 0 is treated as 1.
+    pub fn scan_stream(
+        &self,
+        query: ScanQuery,
+        page_size: usize,
+    ) -> impl Stream<Item = Result<TimedSymbolValues>> + '_ {
+        let page_size = page_size.max(1);
+        let offset = query.range.map_or(0, |(offset, _)| offset);
+        let end = query
+            .range
+            .map(|(offset, limit)| offset.saturating_add(limit));
+        stream::try_unfold(
+            (query, offset, end),
+            move |(query, offset, end)| async move {
+                let limit = end.map_or(page_size, |end| page_size.min(end.saturating_sub(offset)));
+                if limit == 0 {
+                    return Ok::<_, Error>(None);
                 }
-                symbol_values_vec.push(symbol_values);
-            }
-        }
-        Ok(symbol_values_vec)
+                let page = self.scan(&query.clone().range(offset, limit)).await?;
+                if page.symbols.is_empty() {
+                    return Ok(None);
+                }
+                let next_offset = offset + page.symbols.len();
+                let end = match page.total_count {
+                    Some(total_count) => Some(end.map_or(total_count, |end| end.min(total_count))),
+                    None if page.symbols.len() < limit => Some(next_offset),
+                    None => end,
+                };
+                let symbols = stream::iter(page.symbols.into_iter().map(Ok));
+                Ok(Some((symbols, (query, next_offset, end))))
+            },
+        )
+        .try_flatten()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::MockScanner;
+    use anyhow::{Context, Result};
+    use futures::StreamExt;
+    use reqwest::header::RETRY_AFTER;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_builder() -> Result<()> {
+        let tradingview = TradingView::builder()
+            .screener("Crypto")
+            .scanner_url("http://127.0.0.1:8080/scanner")
+            .user_agent("easytradeview")
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        assert_eq!(tradingview.screener, "crypto");
+        assert_eq!(
+            tradingview.scanner_url.as_str(),
+            "http://127.0.0.1:8080/scanner/"
+        );
+        assert_eq!(tradingview.headers[USER_AGENT], "easytradeview");
+        assert_eq!(
+            tradingview.screener_url("crypto", "scan")?.as_str(),
+            "http://127.0.0.1:8080/scanner/crypto/scan"
+        );
+
+        assert!(matches!(
+            TradingView::builder().scanner_url("not a url").build(),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            TradingView::builder().user_agent("bad\nagent").build(),
+            Err(Error::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            TradingView::builder().requests_per_second(0.0).build(),
+            Err(Error::InvalidRateLimit(_))
+        ));
+        assert!(matches!(
+            TradingView::builder().max_concurrent_requests(0).build(),
+            Err(Error::InvalidRateLimit(_))
+        ));
+        let retry_policy = RetryPolicy {
+            multiplier: -1.0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            TradingView::builder().retry_policy(retry_policy).build(),
+            Err(Error::InvalidRetryPolicy(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_without_screener() -> Result<()> {
+        let tradingview = TradingView::builder()
+            .scanner_url("http://127.0.0.1:8080/scanner")
+            .build()?;
+        let query = ScanQuery::new(&[Field::Close.with_interval(&Interval::Day1)]);
+        assert!(matches!(
+            tradingview.scan(&query).await,
+            Err(Error::InvalidScreener(_))
+        ));
+        assert!(matches!(
+            tradingview.metainfo("").await,
+            Err(Error::InvalidScreener(_))
+        ));
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_get_symbol_fields() -> Result<()> {
-        let tradingview = TradingView::new(Screener::Crypto, "OKX");
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
         let interval = Interval::Hour1;
-        let fields: Vec<Field> = vec![]
+        let fields: Vec<Field> = []
             .iter()
             .chain(Field::recommends().iter())
             .chain(Field::oscillator_indicators().iter())
@@ -278,7 +956,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_symbol_fields_with_interval() -> Result<()> {
-        let tradingview = TradingView::new(Screener::Crypto, "OKX");
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
         let interval = Interval::Hour1;
         let fields: Vec<FieldWithInterval> = vec![]
             .into_iter()
@@ -298,31 +977,32 @@ mod tests {
 
     #[tokio::test]
     async fn test_search_symbols() -> Result<()> {
-        let tradingview = TradingView::new(&Screener::Crypto, "");
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
         let interval = Interval::Hour1;
         let exchanges: Vec<&str> = vec![];
         let types: Vec<&str> = vec!["spot"];
-        let name_filter = "USDT";
         let extra_fields: Vec<FieldWithInterval> =
             vec![Field::Open, Field::Close, Field::ChangeFromOpen]
                 .into_iter()
                 .map(|x| x.with_interval(&interval))
                 .collect();
         let data = tradingview
-            .search_symbols(&exchanges, &types, name_filter, extra_fields.as_slice())
+            .search_symbols(&exchanges, &types, "USDT", extra_fields.as_slice())
             .await
             .context("search symbols error")?;
 
-        assert!(data.len() > 0);
+        assert!(!data.is_empty());
         Ok(())
     }
 
     #[tokio::test]
     async fn test_scan_symbols() -> Result<()> {
-        let tradingview = TradingView::new(Screener::Crypto, "OKX");
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
         let interval = Interval::Hour1;
         let symbols = ["BTCUSDT.P"];
-        let fields: Vec<FieldWithInterval> = vec![Field::Name, Field::Exchange, Field::SubType]
+        let fields: Vec<FieldWithInterval> = [Field::Name, Field::Exchange, Field::SubType]
             .iter()
             .chain(Field::oscillator_indicators().iter())
             .chain(Field::move_average_indicators().iter())
@@ -337,4 +1017,617 @@ mod tests {
         assert_eq!(data[0].values().len(), fields.len());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_scan() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
+        let count = mock
+            .dataset()
+            .symbols
+            .iter()
+            .filter(|x| x.screener == "crypto")
+            .count();
+        let volume = Field::RelativeVolume10dCalc.with_interval(&Interval::Day1);
+        let query = ScanQuery::new(&[Field::Name.with_interval(&Interval::Day1), volume.clone()])
+            .sort_by(volume, Order::Desc);
+
+        let page = tradingview
+            .scan(&query.clone().range(0, 20))
+            .await
+            .context("scan error")?;
+        assert_eq!(page.symbols.len(), 20);
+        assert_eq!(page.total_count, Some(count));
+
+        let page = tradingview.scan(&query.range(20, usize::MAX)).await?;
+        assert_eq!(page.symbols.len(), count - 20);
+        assert_eq!(mock.request_count(), 2);
+        Ok(())
+    }
+
+    async fn collect_stream(
+        tradingview: &TradingView,
+        query: ScanQuery,
+        page_size: usize,
+    ) -> Result<Vec<TimedSymbolValues>> {
+        let symbols = tradingview
+            .scan_stream(query, page_size)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_, Error>>()
+            .context("scan stream error")?;
+        Ok(symbols)
+    }
+
+    #[tokio::test]
+    async fn test_scan_stream() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
+        let volume = Field::RelativeVolume10dCalc.with_interval(&Interval::Day1);
+        let query = ScanQuery::new(&[Field::Name.with_interval(&Interval::Day1)])
+            .sort_by(volume, Order::Desc);
+
+        let symbols = collect_stream(&tradingview, query.clone().range(0, 25), 10).await?;
+        assert_eq!(symbols.len(), 25);
+        assert_eq!(mock.request_count(), 3);
+
+        // The 30 crypto symbols of the fixture, in pages of 10, 10 and 10.
+        let all = collect_stream(&tradingview, query.clone(), 10).await?;
+        assert_eq!(all.len(), 30);
+        assert_eq!(mock.request_count(), 6);
+        assert_eq!(&all[..25], &symbols[..]);
+
+        let symbols = collect_stream(&tradingview, query.clone().range(5, usize::MAX), 10).await?;
+        assert_eq!(symbols.len(), 25);
+        assert_eq!(mock.request_count(), 9);
+
+        // A page size of 0 fetches one symbol per request.
+        let symbols = collect_stream(&tradingview, query.range(0, 3), 0).await?;
+        assert_eq!(&symbols[..], &all[..3]);
+        assert_eq!(mock.request_count(), 12);
+        Ok(())
+    }
+
+    /// Serves `count` symbols in the requested range, without reporting the total count.
+    #[derive(Debug)]
+    struct UncountedTransport {
+        count: usize,
+        requests: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for UncountedTransport {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let body: Value = serde_json::from_slice(request.body.as_deref().unwrap_or_default())?;
+            let from = body["range"][0].as_u64().unwrap_or(0) as usize;
+            let to = body["range"][1].as_u64().unwrap_or(0) as usize;
+            let data: Vec<Value> = (from..to.min(self.count))
+                .map(|i| json!({"s": format!("OKX:SYM{}", i), "d": [i]}))
+                .collect();
+            let body = serde_json::to_vec(&json!({ "data": data }))?;
+            Ok(TransportResponse::new(StatusCode::OK, body))
+        }
+    }
+
+    /// Answers with the values of a symbol after a delay, keeping track of the requests in flight.
+    #[derive(Debug)]
+    struct SlowTransport {
+        delay: Duration,
+        requests: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    impl SlowTransport {
+        fn new(delay: Duration) -> Self {
+            Self {
+                delay,
+                requests: AtomicUsize::new(0),
+                in_flight: AtomicUsize::new(0),
+                max_in_flight: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for SlowTransport {
+        async fn send(&self, _request: TransportRequest) -> crate::Result<TransportResponse> {
+            let request = self.requests.fetch_add(1, Ordering::SeqCst) + 1;
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            let body = serde_json::to_vec(&json!({ "close": request }))?;
+            Ok(TransportResponse::new(StatusCode::OK, body))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_shared_by_clones() -> Result<()> {
+        let transport = Arc::new(SlowTransport::new(Duration::from_millis(50)));
+        let tradingview = TradingView::builder()
+            .exchange("OKX")
+            .max_concurrent_requests(1)
+            .transport(transport.clone())
+            .build()?;
+        let clone = tradingview.clone();
+
+        let (first, second) = futures::join!(
+            tradingview.get_symbol_fields("BTCUSDT", "1d", &[Field::Close]),
+            clone.get_symbol_fields("ETHUSDT", "1d", &[Field::Close]),
+        );
+        first?;
+        second?;
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 2);
+        assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    /// Answers with the given responses in turn, then with the values of a symbol.
+    #[derive(Debug, Default)]
+    struct ScriptedTransport {
+        responses: Mutex<VecDeque<crate::Result<TransportResponse>>>,
+        requests: AtomicUsize,
+    }
+
+    impl ScriptedTransport {
+        fn push(&self, status: StatusCode, retry_after: Option<&'static str>) {
+            let mut response = TransportResponse::new(status, status.as_str().into());
+            if let Some(retry_after) = retry_after {
+                response
+                    .headers
+                    .insert(RETRY_AFTER, HeaderValue::from_static(retry_after));
+            }
+            self.responses.lock().unwrap().push_back(Ok(response));
+        }
+
+        fn push_err(&self, err: Error) {
+            self.responses.lock().unwrap().push_back(Err(err));
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for ScriptedTransport {
+        async fn send(&self, _request: TransportRequest) -> crate::Result<TransportResponse> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let response = self.responses.lock().unwrap().pop_front();
+            response.unwrap_or_else(|| {
+                Ok(TransportResponse::new(
+                    StatusCode::OK,
+                    br#"{"close": 1.5}"#.to_vec(),
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry() -> Result<()> {
+        let transport = Arc::new(ScriptedTransport::default());
+        let tradingview = TradingView::builder()
+            .exchange("OKX")
+            .retry_policy(
+                RetryPolicy::default()
+                    .max_attempts(3)
+                    .backoff(Duration::from_millis(1), Duration::from_millis(10)),
+            )
+            .transport(transport.clone())
+            .build()?;
+        let get = || tradingview.get_symbol_fields("BTCUSDT", "1d", &[Field::Close]);
+
+        transport.push(StatusCode::SERVICE_UNAVAILABLE, None);
+        assert_eq!(get().await?.values()[&Field::Close], 1.5);
+        assert_eq!(transport.requests(), 2);
+
+        transport.push(StatusCode::BAD_REQUEST, None);
+        assert!(matches!(get().await, Err(Error::Http { status: 400, .. })));
+        assert_eq!(transport.requests(), 3);
+
+        // Retry-After is capped by the maximum backoff, so this doesn't wait for an hour.
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            transport.push(StatusCode::TOO_MANY_REQUESTS, Some("3600"));
+        }
+        match get().await {
+            Err(Error::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(3600)))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(transport.requests(), 6);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_custom_transport_error() -> Result<()> {
+        let transport = Arc::new(ScriptedTransport::default());
+        let tradingview = TradingView::builder()
+            .exchange("OKX")
+            .retry_policy(
+                RetryPolicy::default().backoff(Duration::from_millis(1), Duration::from_millis(10)),
+            )
+            .transport(transport.clone())
+            .build()?;
+        let get = || tradingview.get_symbol_fields("BTCUSDT", "1d", &[Field::Close]);
+
+        transport.push_err(Error::custom_transport("connection refused", true));
+        assert_eq!(get().await?.values()[&Field::Close], 1.5);
+        assert_eq!(transport.requests(), 2);
+
+        transport.push_err(Error::custom_transport("invalid request", false));
+        match get().await {
+            Err(err @ Error::CustomTransport { .. }) => {
+                assert!(!err.is_transient());
+                assert_eq!(
+                    format!("{:#}", anyhow::Error::from(err)),
+                    "Transport error: invalid request"
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(transport.requests(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_stream_without_total_count() -> Result<()> {
+        let transport = Arc::new(UncountedTransport {
+            count: 23,
+            requests: AtomicUsize::new(0),
+        });
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .transport(transport.clone())
+            .build()?;
+        let query = ScanQuery::new(&[Field::Close.with_interval(&Interval::Day1)]);
+
+        let symbols = collect_stream(&tradingview, query.clone(), 10).await?;
+        assert_eq!(symbols.len(), 23);
+        assert_eq!(symbols[22].symbol(), &Ticker::new("OKX", "SYM22"));
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 3);
+
+        // A full last page takes one more request to find out there is nothing after it.
+        let symbols = collect_stream(&tradingview, query.range(3, 100), 10).await?;
+        assert_eq!(symbols.len(), 20);
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 3 + 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scan_symbols_mixed_exchanges() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "");
+        let tickers = [
+            Ticker::new("BINANCE", "BTCUSDT"),
+            Ticker::new("OKX", "BTCUSDT"),
+            Ticker::new("BYBIT", "BTCUSDT"),
+        ];
+        let fields = [Field::Close.with_interval(&Interval::Day1)];
+        let data = tradingview
+            .scan_symbols(&tickers, &fields)
+            .await
+            .context("scan symbols error")?;
+        assert_eq!(data.len(), tickers.len());
+        for values in data.iter() {
+            assert!(tickers.contains(values.symbol()));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cassette() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("easytradeview-{}.json", std::process::id()));
+        let fields = [Field::Close.with_interval(&Interval::Hour1)];
+        let mock = MockScanner::start().await?;
+        let url = mock.url();
+
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .exchange("OKX")
+            .scanner_url(&url)
+            .cassette(Cassette::record(&path))
+            .build()?;
+        let recorded = Analysis::get_technical_analysis(&tradingview, "BTCUSDT.P", "60").await?;
+        let scanned = tradingview.scan_symbols(&["BTCUSDT"], &fields).await?;
+        assert_eq!(tradingview.cassette().map(|x| x.len()), Some(2));
+        assert!(!path.exists());
+        tradingview.cassette().context("no cassette")?.save()?;
+        drop(mock);
+
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .exchange("OKX")
+            .scanner_url(&url)
+            .cassette(Cassette::replay(&path)?)
+            .build()?;
+        let replayed = Analysis::get_technical_analysis(&tradingview, "BTCUSDT.P", "60").await?;
+        assert_eq!(
+            serde_json::to_value(recorded)?,
+            serde_json::to_value(replayed)?
+        );
+        let rescanned = tradingview.scan_symbols(&["BTCUSDT"], &fields).await?;
+        assert_eq!(scanned[0].values(), rescanned[0].values());
+        assert!(matches!(
+            tradingview.scan_symbols(&["ETHUSDT"], &fields).await,
+            Err(Error::CassetteMiss(_))
+        ));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .exchange("BINANCE")
+            .scanner_url(mock.url())
+            .cache(ResponseCache::new(Duration::from_secs(60)))
+            .build()?;
+        let close = Field::Close.with_interval(&Interval::Hour1);
+        let open = Field::Open.with_interval(&Interval::Hour1);
+
+        let first = tradingview
+            .get_symbol_fields_with_interval("BTCUSDT", std::slice::from_ref(&close))
+            .await?;
+        let second = tradingview
+            .get_symbol_fields_with_interval("BTCUSDT", &[close.clone(), open.clone()])
+            .await?;
+        assert_eq!(first.values()[&close], second.values()[&close]);
+        assert_eq!(second.values().len(), 2);
+        assert_eq!(mock.request_count(), 2);
+
+        let symbols = ["BTCUSDT", "ETHUSDT", "UNKNOWN"];
+        let data = tradingview
+            .scan_symbols(&symbols, &[close.clone(), open.clone()])
+            .await?;
+        assert_eq!(mock.request_count(), 3);
+        let data_tickers: Vec<String> = data.iter().map(|x| x.symbol().to_string()).collect();
+        assert_eq!(data_tickers, ["BINANCE:BTCUSDT", "BINANCE:ETHUSDT"]);
+        assert_eq!(data[0].values()[&open], second.values()[&open]);
+
+        tradingview
+            .scan_symbols(&symbols[..2], &[close, open])
+            .await?;
+        assert_eq!(mock.request_count(), 3);
+
+        let cache = tradingview.cache().unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (7, 6));
+        Ok(())
+    }
+
+    /// Forwards requests over HTTP, reversing the rows of scan responses.
+    #[derive(Debug, Default)]
+    struct ReversingTransport {
+        inner: ReqwestTransport,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for ReversingTransport {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            let mut response = self.inner.send(request).await?;
+            let mut json: Value = serde_json::from_slice(&response.body)?;
+            if let Some(data) = json["data"].as_array_mut() {
+                data.reverse();
+            }
+            response.body = serde_json::to_vec(&json)?;
+            Ok(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_symbols_order() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let builder = || {
+            TradingView::builder()
+                .screener(Screener::Crypto)
+                .exchange("BINANCE")
+                .scanner_url(mock.url())
+                .transport(Arc::new(ReversingTransport::default()))
+        };
+        let symbols = ["ETHUSDT", "UNKNOWN", "BTCUSDT", "ETHUSDT", "SOLUSDT"];
+        let fields = [Field::Close.with_interval(&Interval::Hour1)];
+        let expected = ["BINANCE:ETHUSDT", "BINANCE:BTCUSDT", "BINANCE:SOLUSDT"];
+        let tickers = |data: Vec<TimedSymbolValues>| -> Vec<String> {
+            data.iter().map(|x| x.symbol().to_string()).collect()
+        };
+
+        let uncached = builder().build()?;
+        let data = uncached.scan_symbols(&symbols, &fields).await?;
+        assert_eq!(tickers(data), expected);
+
+        let cached = builder()
+            .cache(ResponseCache::new(Duration::from_secs(60)))
+            .build()?;
+        cached
+            .get_symbol_fields_with_interval("SOLUSDT", &fields)
+            .await?;
+        for _ in 0..2 {
+            let data = cached.scan_symbols(&symbols, &fields).await?;
+            assert_eq!(tickers(data), expected);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_requests() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
+        let fields = [Field::Close, Field::RSI];
+        let requests = (0..8).map(|_| tradingview.get_symbol_fields("BTCUSDT.P", "60", &fields));
+        let results = futures::future::join_all(requests).await;
+        assert_eq!(mock.request_count(), 1);
+        for result in results {
+            assert_eq!(result?.values().len(), fields.len());
+        }
+
+        tradingview
+            .get_symbol_fields("BTCUSDT.P", "60", &fields)
+            .await?;
+        assert_eq!(mock.request_count(), 2);
+        assert!(tradingview.in_flight.lock().unwrap().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancel_coalesced_request() -> Result<()> {
+        let transport = Arc::new(SlowTransport::new(Duration::from_millis(200)));
+        let tradingview = TradingView::builder()
+            .exchange("OKX")
+            .transport(transport.clone())
+            .build()?;
+        let get = || tradingview.get_symbol_fields("BTCUSDT", "1d", &[Field::Close]);
+        let requests = || transport.requests.load(Ordering::SeqCst);
+
+        let cancelled = tokio::time::timeout(Duration::from_millis(20), get()).await;
+        assert!(cancelled.is_err());
+        assert!(tradingview.in_flight.lock().unwrap().is_empty());
+
+        // A new call doesn't join the cancelled request, but sends its own.
+        assert_eq!(get().await?.values()[&Field::Close], 2);
+        assert_eq!(requests(), 2);
+        assert!(tradingview.in_flight.lock().unwrap().is_empty());
+
+        // Cancelling one of two calls leaves the request to the other.
+        let (cancelled, completed) = futures::join!(
+            tokio::time::timeout(Duration::from_millis(20), get()),
+            get()
+        );
+        assert!(cancelled.is_err());
+        assert_eq!(completed?.values()[&Field::Close], 3);
+        assert_eq!(requests(), 3);
+        assert!(tradingview.in_flight.lock().unwrap().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunk_requests() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .exchange("BINANCE")
+            .scanner_url(mock.url())
+            .max_fields_per_request(10)
+            .max_tickers_per_request(2)
+            .parallelism(2)
+            .build()?;
+        let fields: Vec<FieldWithInterval> = Field::oscillator_indicators()
+            .iter()
+            .cloned()
+            .map(|x| x.with_interval(&Interval::Hour1))
+            .collect();
+
+        let data = tradingview
+            .get_symbol_fields_with_interval("BTCUSDT", &fields)
+            .await?;
+        assert_eq!(data.values().len(), fields.len());
+        assert_eq!(mock.request_count(), 3);
+
+        let symbols = ["BTCUSDT", "ETHUSDT", "SOLUSDT", "BNBUSDT", "XRPUSDT"];
+        let data = tradingview.scan_symbols(&symbols, &fields).await?;
+        assert_eq!(mock.request_count(), 3 + 3 * 3);
+        assert_eq!(data.len(), symbols.len());
+        for (values, symbol) in data.iter().zip(symbols) {
+            assert_eq!(values.symbol(), &Ticker::new("BINANCE", symbol));
+            assert_eq!(values.values().len(), fields.len());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unknown_symbols() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "BINANCE");
+        let close = Field::Close.with_interval(&Interval::Day1);
+        let pivot = Field::PivotMClassicS3.with_interval(&Interval::Day1);
+
+        let results = tradingview
+            .scan_symbols_batch(
+                &["BTCUSDT", "BTCUSTD", "ETHUSDT"],
+                std::slice::from_ref(&close),
+            )
+            .await?;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().symbol().symbol, "BTCUSDT");
+        assert!(matches!(&results[1], Err(Error::UnknownSymbol(x)) if x == "BINANCE:BTCUSTD"));
+        assert_eq!(results[2].as_ref().unwrap().symbol().symbol, "ETHUSDT");
+
+        let results = tradingview
+            .scan_symbols_batch(&["ETHUSDT"], std::slice::from_ref(&pivot))
+            .await?;
+        assert!(matches!(&results[0], Err(Error::NoData(_))));
+
+        assert!(matches!(
+            tradingview
+                .get_symbol_fields("BTCUSTD", "1d", &[Field::Close])
+                .await,
+            Err(Error::UnknownSymbol(_))
+        ));
+        assert!(matches!(
+            tradingview
+                .get_symbol_fields("BTCUSDT", "3h", &[Field::Close])
+                .await,
+            Err(Error::InvalidInterval(x)) if x == "3h"
+        ));
+        assert!(matches!(
+            tradingview
+                .get_symbol_fields_with_interval("ETHUSDT", &[pivot])
+                .await,
+            Err(Error::NoData(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "BINANCE");
+        let info = tradingview.resolve("BTCUSDT").await?;
+        assert_eq!(info.ticker, Ticker::new("BINANCE", "BTCUSDT"));
+        assert_eq!(info.name, "BTCUSDT");
+        assert_eq!(info.symbol_type, "spot");
+        assert_eq!(info.description, "Bitcoin / TetherUS");
+        assert_eq!(info.currency, "USDT");
+        assert_eq!(info.min_tick(), Some(0.01));
+        assert!(!info.fractional);
+
+        assert!(matches!(
+            tradingview.resolve("NASDAQ:AAPL").await,
+            Err(Error::UnknownSymbol(_))
+        ));
+        let tradingview = mock.tradingview(Screener::America, "NASDAQ");
+        assert_eq!(tradingview.resolve("AAPL").await?.symbol_type, "stock");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metainfo() -> Result<()> {
+        let mock = MockScanner::start().await?;
+        let tradingview = mock.tradingview(Screener::Crypto, "OKX");
+        let fields = tradingview.metainfo(Screener::Crypto).await?;
+
+        let field = |field: Field| fields.iter().find(|x| x.field() == field).cloned();
+        let rsi = field(Field::RSI).context("no RSI")?;
+        assert!(rsi.has_interval);
+        assert_eq!(rsi.field_type, FieldType::Number);
+        let symbol_type = field(Field::Type).context("no type")?;
+        assert!(!symbol_type.has_interval);
+        assert_eq!(symbol_type.field_type, FieldType::Text);
+        assert_eq!(
+            symbol_type.enum_values,
+            Some(vec!["spot".to_owned(), "swap".to_owned()])
+        );
+
+        assert!(matches!(
+            tradingview.metainfo("undefined").await,
+            Err(Error::Http { status: 404, .. })
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,139 @@
+use crate::Result;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode, Url};
+use std::time::Duration;
+
+/// A request to the scanner, as handed to a `Transport`.
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+    pub timeout: Option<Duration>,
+}
+
+/// A response of the scanner, as returned by a `Transport`.
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    /// Creates a new `TransportResponse` instance without headers.
+    pub fn new(status: StatusCode, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body,
+        }
+    }
+}
+
+/// Sends requests to the scanner, e.g. over HTTP with reqwest, or to an in-process fake.
+/// Non-success statuses are returned as responses; errors are reserved for failures to get a response at all,
+/// reported by custom transports with `Error::custom_transport`.
+#[async_trait]
+pub trait Transport: std::fmt::Debug + Send + Sync {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse>;
+}
+
+/// The default transport, sending requests over HTTP with a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a new `ReqwestTransport` instance with a custom reqwest::Client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use anyhow::Result;
+    use reqwest::header::CONTENT_TYPE;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct FakeTransport {
+        requests: Mutex<Vec<TransportRequest>>,
+    }
+
+    #[async_trait]
+    impl Transport for FakeTransport {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            let response = match request.url.path() {
+                "/symbol" => TransportResponse::new(StatusCode::OK, br#"{"close": 1.5}"#.to_vec()),
+                _ => TransportResponse::new(StatusCode::BAD_REQUEST, b"bad request".to_vec()),
+            };
+            self.requests.lock().unwrap().push(request);
+            Ok(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() -> Result<()> {
+        let transport = std::sync::Arc::new(FakeTransport::default());
+        let tradingview = TradingView::builder()
+            .screener(Screener::Crypto)
+            .exchange("OKX")
+            .timeout(Duration::from_secs(3))
+            .transport(transport.clone())
+            .build()?;
+
+        let values = tradingview
+            .get_symbol_fields("BTCUSDT", "1d", &[Field::Close])
+            .await?;
+        assert_eq!(values.values()[&Field::Close], 1.5);
+
+        let query = ScanQuery::new(&[Field::Close.with_interval(&Interval::Day1)]);
+        match tradingview.scan(&query).await {
+            Err(Error::Http { status, body }) => {
+                assert_eq!((status, body.as_str()), (400, "bad request"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, Method::GET);
+        assert_eq!(requests[0].timeout, Some(Duration::from_secs(3)));
+        assert_eq!(requests[1].url.path(), "/crypto/scan");
+        assert_eq!(requests[1].headers[CONTENT_TYPE], "application/json");
+        Ok(())
+    }
+}